@@ -0,0 +1,491 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use commands;
+use error::{GhError, GhResult};
+use evidence::json_ops;
+use git_hub::issues::{Issue, IssueUser};
+use hyper::status::StatusCode;
+
+pub fn SUBCOMMAND<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("issue")
+                .about("List, View, Create, Close, Reopen and Comment on GitHub Issues.")
+                .version(version!())
+                .author("penland365 <Jeffrey.N.Davis@gmail.com>")
+                .subcommand(SubCommand::with_name("list")
+                                        .about("Lists the Issues of a repository. Pull Requests are left out.")
+                                        .arg(Arg::with_name("state")
+                                             .short("s")
+                                             .long("state")
+                                             .help("Filters Issues by state.")
+                                             .possible_values(&["open", "closed", "all"])
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("label")
+                                             .short("l")
+                                             .long("label")
+                                             .help("Only lists Issues with this label. May be repeated or comma separated.")
+                                             .value_name("name")
+                                             .takes_value(true)
+                                             .multiple(true)
+                                             .number_of_values(1)
+                                             .use_delimiter(true))
+                                        .arg(Arg::with_name("assignee")
+                                             .short("a")
+                                             .long("assignee")
+                                             .help("Filters Issues by assignee. Use none for unassigned Issues, * for any assignee.")
+                                             .value_name("login")
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("milestone")
+                                             .short("m")
+                                             .long("milestone")
+                                             .help("Filters Issues by milestone number. Use none for no milestone, * for any milestone.")
+                                             .value_name("number")
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("creator")
+                                             .short("c")
+                                             .long("creator")
+                                             .help("Filters Issues by the user who opened them.")
+                                             .value_name("login")
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("since")
+                                             .long("since")
+                                             .help("Only lists Issues updated at or after this time.")
+                                             .value_name("YYYY-MM-DDTHH:MM:SSZ")
+                                             .takes_value(true))
+                                        .arg(commands::format_arg())
+                                        .args(&commands::pagination_args()))
+                .subcommand(SubCommand::with_name("view")
+                                        .about("Shows a single Issue.")
+                                        .arg(number_arg())
+                                        .arg(Arg::with_name("comments")
+                                             .long("comments")
+                                             .help("Also shows the comments on the Issue."))
+                                        .arg(commands::format_arg()))
+                .subcommand(SubCommand::with_name("create")
+                                        .about("Opens a new Issue.")
+                                        .arg(Arg::with_name("title")
+                                                  .required(true)
+                                                  .help("The title of the Issue."))
+                                        .args(&body_args())
+                                        .arg(Arg::with_name("label")
+                                             .short("l")
+                                             .long("label")
+                                             .help("Adds this label. May be repeated or comma separated.")
+                                             .value_name("name")
+                                             .takes_value(true)
+                                             .multiple(true)
+                                             .number_of_values(1)
+                                             .use_delimiter(true))
+                                        .arg(Arg::with_name("assignee")
+                                             .short("a")
+                                             .long("assignee")
+                                             .help("Assigns this user. May be repeated or comma separated.")
+                                             .value_name("login")
+                                             .takes_value(true)
+                                             .multiple(true)
+                                             .number_of_values(1)
+                                             .use_delimiter(true))
+                                        .arg(Arg::with_name("milestone")
+                                             .short("m")
+                                             .long("milestone")
+                                             .help("Adds the Issue to the milestone with this number.")
+                                             .value_name("number")
+                                             .takes_value(true)
+                                             .validator(commands::is_positive_number))
+                                        .arg(commands::format_arg()))
+                .subcommand(SubCommand::with_name("close")
+                                        .about("Closes an Issue.")
+                                        .arg(number_arg())
+                                        .arg(Arg::with_name("reason")
+                                             .short("r")
+                                             .long("reason")
+                                             .help("Why the Issue is being closed.")
+                                             .possible_values(&["completed", "not_planned"])
+                                             .takes_value(true))
+                                        .arg(commands::format_arg()))
+                .subcommand(SubCommand::with_name("reopen")
+                                        .about("Reopens a closed Issue.")
+                                        .arg(number_arg())
+                                        .arg(commands::format_arg()))
+                .subcommand(SubCommand::with_name("comment")
+                                        .about("Comments on an Issue.")
+                                        .arg(number_arg())
+                                        .args(&body_args())
+                                        .arg(commands::format_arg()))
+}
+
+pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+    match matches.subcommand() {
+        ("list", Some(list_matches))       => list::handle(list_matches),
+        ("view", Some(view_matches))       => view::handle(view_matches),
+        ("create", Some(create_matches))   => create::handle(create_matches),
+        ("close", Some(close_matches))     => state::handle(close_matches, "closed"),
+        ("reopen", Some(reopen_matches))   => state::handle(reopen_matches, "open"),
+        ("comment", Some(comment_matches)) => comment::handle(comment_matches),
+        ("", None)                         => {
+            println!("No subcommand was used for issue");
+            Ok(())
+        },
+        (_, _)                             => unreachable!()
+    }
+}
+
+fn number_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("number")
+        .required(true)
+        .help("The number of the Issue.")
+        .validator(commands::is_positive_number)
+}
+
+fn body_args<'a, 'b>() -> Vec<Arg<'a, 'b>> {
+    vec![
+        Arg::with_name("body")
+            .short("b")
+            .long("body")
+            .help("The text of the body.")
+            .value_name("text")
+            .takes_value(true)
+            .conflicts_with_all(&["body-file", "editor"]),
+        Arg::with_name("body-file")
+            .short("F")
+            .long("body-file")
+            .help("Reads the body from a file. Use - to read from stdin.")
+            .value_name("path")
+            .takes_value(true)
+            .conflicts_with("editor"),
+        Arg::with_name("editor")
+            .short("e")
+            .long("editor")
+            .help("Writes the body in $EDITOR.")
+    ]
+}
+
+// The login of an author, or ghost once their account has been deleted
+fn login(user: &Option<IssueUser>) -> &str {
+    user.as_ref().map(|x| x.login.as_str()).unwrap_or("ghost")
+}
+
+fn explain_error(error: GhError) -> GhError {
+    match error {
+        GhError::Api(StatusCode::Gone, _) => GhError::Http(StatusCode::Gone, GONE.to_owned()),
+        error                             => commands::explain_error(error, FORBIDDEN, NOT_FOUND)
+    }
+}
+
+// The output of commands that change a single Issue: its number and URL.
+fn format_issue(issue: Issue, is_json: bool) -> GhResult<String> {
+    if is_json {
+        json_ops::to_pretty_json(&issue, SERIALIZE_ISSUE)
+    } else {
+        Ok(format!("{0: <8} {1: <50}", issue.number, issue.html_url))
+    }
+}
+
+const SERIALIZE_ISSUE: &'static str = "Error serializing GitHub Issue JSON.";
+
+const FORBIDDEN: &'static str = "403 Forbidden. Does your OAuth token have sufficient scope? A minimum of `public_repo` or `repo` is required. See https://developer.github.com/v3/issues/";
+const NOT_FOUND: &'static str = "404 Not Found. Does the repository or Issue exist, and can your credentials see it? See https://developer.github.com/v3/issues/";
+const GONE: &'static str = "410 Gone. Issues are disabled for this repository.";
+
+mod list {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, NL, pagination_from_args, repo_from_args, values};
+use error::GhResult;
+use evidence::json_ops;
+use git_hub::issues;
+use git_hub::issues::{Issue, IssueFilters};
+use super::{explain_error, login};
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let client = client_from_args(matches)?;
+        let (owner, repo) = repo_from_args(matches, &client)?;
+        let filters = IssueFilters {
+            state: matches.value_of("state").map(|x| x.to_owned()),
+            labels: values(matches, "label"),
+            assignee: matches.value_of("assignee").map(|x| x.to_owned()),
+            milestone: matches.value_of("milestone").map(|x| x.to_owned()),
+            creator: matches.value_of("creator").map(|x| x.to_owned()),
+            since: matches.value_of("since").map(|x| x.to_owned())
+        };
+        let pagination = pagination_from_args(matches);
+        let issues = issues::list_issues(&owner, &repo, &filters, &client, &pagination)
+                             .map_err(explain_error)?;
+        let output = &format_output(issues, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    fn format_output(issues: Vec<Issue>, is_json: bool) -> GhResult<String> {
+        if is_json {
+            json_ops::to_pretty_json(&issues, SERIALIZE_ISSUES)
+        } else {
+            let mut output = String::with_capacity(100);
+            let header = format!("{0: <8} {1: <8} {2: <15} {3: <25} {4}", "number", "state", "author", "labels", "title");
+            output.push_str(&header);
+            output.push_str(NL);
+            for issue in issues {
+                let labels: Vec<String> = issue.labels.into_iter().map(|x| x.name).collect();
+                let line = format!("{0: <8} {1: <8} {2: <15} {3: <25} {4}",
+                                   issue.number, issue.state, login(&issue.user), labels.join(","), issue.title);
+                output.push_str(&line);
+                output.push_str(NL);
+            }
+            Ok(output)
+        }
+    }
+
+    const SERIALIZE_ISSUES: &'static str = "Error serializing GitHub Issue JSON.";
+
+    #[cfg(test)]
+    mod tests {
+    use evidence::json_ops;
+    use super::format_output;
+
+        #[test]
+        fn test_format_output_table() -> () {
+            let body = "[{\"id\":1,\"number\":1347,\"state\":\"open\",\"title\":\"Found a bug\",\
+                        \"body\":\"I'm having a problem with this.\",\
+                        \"user\":{\"login\":\"octocat\",\"id\":1},\
+                        \"labels\":[{\"name\":\"bug\",\"color\":\"f29513\"},{\"name\":\"ui\",\"color\":\"cccccc\"}],\
+                        \"assignees\":[],\"milestone\":null,\"comments\":0,\
+                        \"url\":\"https://api.github.com/repos/octocat/Hello-World/issues/1347\",\
+                        \"html_url\":\"https://github.com/octocat/Hello-World/issues/1347\",\
+                        \"created_at\":\"2011-04-22T13:33:48Z\",\"updated_at\":\"2011-04-22T13:33:48Z\",\
+                        \"closed_at\":null}]";
+            let output = format_output(json_ops::from_str(body, "").unwrap(), false).unwrap();
+            let lines: Vec<&str> = output.trim().lines().collect();
+            assert_eq!(lines.len(), 2);
+            assert!(lines[1].starts_with("1347"));
+            assert!(lines[1].contains("bug,ui"));
+            assert!(lines[1].ends_with("Found a bug"));
+        }
+    }
+}
+
+mod view {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, NL, repo_from_args};
+use error::GhResult;
+use evidence::json_ops;
+use git_hub::issues;
+use git_hub::issues::{Issue, IssueComment};
+use git_hub::pagination::Pagination;
+use super::{explain_error, login};
+
+    // An Issue together with its comments, for --comments --format json
+    #[derive(Serialize)]
+    struct IssueWithComments {
+        issue: Issue,
+        comments: Vec<IssueComment>
+    }
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let client = client_from_args(matches)?;
+        let (owner, repo) = repo_from_args(matches, &client)?;
+        let number = matches.value_of("number").unwrap().parse().unwrap();
+        let issue  = issues::get_issue(&owner, &repo, number, &client).map_err(explain_error)?;
+        let comments = if matches.is_present("comments") {
+            Some(issues::list_comments(&owner, &repo, number, &client, &Pagination::default()).map_err(explain_error)?)
+        } else {
+            None
+        };
+        let output = &format_output(issue, comments, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    fn format_output(issue: Issue, comments: Option<Vec<IssueComment>>, is_json: bool) -> GhResult<String> {
+        if is_json {
+            return match comments {
+                Some(comments) => json_ops::to_pretty_json(&IssueWithComments { issue: issue, comments: comments },
+                                                           SERIALIZE_ISSUE),
+                None           => json_ops::to_pretty_json(&issue, SERIALIZE_ISSUE)
+            };
+        }
+        let labels: Vec<&str> = issue.labels.iter().map(|x| x.name.as_str()).collect();
+        let assignees: Vec<&str> = issue.assignees.iter().map(|x| x.login.as_str()).collect();
+        let milestone = match issue.milestone {
+            Some(ref milestone) => milestone.title.as_str(),
+            None                => ""
+        };
+        let mut output = String::with_capacity(500);
+        output.push_str(&format!("#{} {}", issue.number, issue.title));
+        output.push_str(NL);
+        for &(name, value) in &[("state", issue.state.as_str()),
+                                ("author", login(&issue.user)),
+                                ("labels", &labels.join(", ")),
+                                ("assignees", &assignees.join(", ")),
+                                ("milestone", milestone),
+                                ("comments", &issue.comments.to_string()),
+                                ("url", issue.html_url.as_str())] {
+            output.push_str(&format!("{0: <10} {1}", name, value));
+            output.push_str(NL);
+        }
+        if let Some(ref body) = issue.body {
+            output.push_str(NL);
+            output.push_str(body.trim());
+            output.push_str(NL);
+        }
+        for comment in comments.unwrap_or(vec![]) {
+            output.push_str(NL);
+            output.push_str(&format!("{} commented at {}", login(&comment.user), comment.created_at));
+            output.push_str(NL);
+            output.push_str(comment.body.trim());
+            output.push_str(NL);
+        }
+        Ok(output)
+    }
+
+    const SERIALIZE_ISSUE: &'static str = "Error serializing GitHub Issue JSON.";
+
+    #[cfg(test)]
+    mod tests {
+    use evidence::json_ops;
+    use super::format_output;
+
+        const ISSUE: &'static str = "{\"id\":1,\"number\":1347,\"state\":\"open\",\"title\":\"Found a bug\",\
+                                     \"body\":\"I'm having a problem with this.\",\
+                                     \"user\":{\"login\":\"octocat\",\"id\":1},\
+                                     \"labels\":[{\"name\":\"bug\",\"color\":\"f29513\"}],\
+                                     \"assignees\":[{\"login\":\"hubot\",\"id\":2}],\
+                                     \"milestone\":{\"number\":1,\"title\":\"v1.0\",\"state\":\"open\"},\"comments\":1,\
+                                     \"url\":\"https://api.github.com/repos/octocat/Hello-World/issues/1347\",\
+                                     \"html_url\":\"https://github.com/octocat/Hello-World/issues/1347\",\
+                                     \"created_at\":\"2011-04-22T13:33:48Z\",\"updated_at\":\"2011-04-22T13:33:48Z\",\
+                                     \"closed_at\":null}";
+
+        #[test]
+        fn test_format_output() -> () {
+            let comments = "[{\"id\":1,\"body\":\"Me too\",\"user\":{\"login\":\"hubot\",\"id\":2},\
+                            \"html_url\":\"https://github.com/octocat/Hello-World/issues/1347#issuecomment-1\",\
+                            \"created_at\":\"2011-04-14T16:00:49Z\",\"updated_at\":\"2011-04-14T16:00:49Z\"}]";
+            let output = format_output(json_ops::from_str(ISSUE, "").unwrap(),
+                                       Some(json_ops::from_str(comments, "").unwrap()),
+                                       false).unwrap();
+            let lines: Vec<&str> = output.trim().lines().collect();
+            assert_eq!(lines[0], "#1347 Found a bug");
+            assert_eq!(lines[3], "labels     bug");
+            assert_eq!(lines[4], "assignees  hubot");
+            assert_eq!(lines[5], "milestone  v1.0");
+            assert_eq!(lines[9], "I'm having a problem with this.");
+            assert_eq!(lines[11], "hubot commented at 2011-04-14T16:00:49Z");
+            assert_eq!(lines[12], "Me too");
+        }
+
+        #[test]
+        fn test_format_output_json_with_comments() -> () {
+            let output = format_output(json_ops::from_str(ISSUE, "").unwrap(), Some(vec![]), true).unwrap();
+            assert!(output.contains("\"issue\""));
+            assert!(output.contains("\"comments\": []"));
+        }
+    }
+}
+
+mod create {
+use clap::ArgMatches;
+use commands::{body_from_args, client_from_args, is_json, repo_from_args, values};
+use error::GhResult;
+use git_hub::issues;
+use git_hub::issues::NewIssue;
+use super::{explain_error, format_issue};
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let client = client_from_args(matches)?;
+        let (owner, repo) = repo_from_args(matches, &client)?;
+        let issue = NewIssue {
+            title: matches.value_of("title").unwrap().to_owned(),
+            body: body_from_args(matches, "issue", "ISSUE_EDITMSG", EDITOR_TEMPLATE)?,
+            labels: values(matches, "label"),
+            assignees: values(matches, "assignee"),
+            milestone: matches.value_of("milestone").map(|x| x.parse().unwrap())
+        };
+        let issue = issues::create_issue(&owner, &repo, &issue, &client)
+                           .map_err(explain_error)?;
+        let output = &format_issue(issue, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    const EDITOR_TEMPLATE: &'static str = "\n# Describe the issue above. This line is ignored.\n";
+}
+
+// Closes or reopens an Issue
+mod state {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, repo_from_args};
+use error::GhResult;
+use git_hub::issues;
+use git_hub::issues::EditIssue;
+use super::{explain_error, format_issue};
+
+    pub fn handle(matches: &ArgMatches, state: &str) -> GhResult<()> {
+        let client = client_from_args(matches)?;
+        let (owner, repo) = repo_from_args(matches, &client)?;
+        let number = matches.value_of("number").unwrap().parse().unwrap();
+        let edit = EditIssue {
+            state: Some(state.to_owned()),
+            state_reason: matches.value_of("reason").map(|x| x.to_owned()),
+            ..EditIssue::default()
+        };
+        let issue = issues::edit_issue(&owner, &repo, number, &edit, &client)
+                           .map_err(explain_error)?;
+        let output = &format_issue(issue, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+}
+
+mod comment {
+use clap::ArgMatches;
+use commands::{body_from_args, client_from_args, is_json, repo_from_args};
+use error::{GhError, GhResult};
+use evidence::json_ops;
+use git_hub::issues;
+use super::explain_error;
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let client = client_from_args(matches)?;
+        let (owner, repo) = repo_from_args(matches, &client)?;
+        let number = matches.value_of("number").unwrap().parse().unwrap();
+        let body = match body_from_args(matches, "comment", "COMMENT_EDITMSG", EDITOR_TEMPLATE)? {
+            Some(ref body) if !body.trim().is_empty() => body.to_owned(),
+            _                                          => return Err(GhError::Usage(NO_BODY.to_owned()))
+        };
+        let comment = issues::create_comment(&owner, &repo, number, &body, &client)
+                             .map_err(explain_error)?;
+        let output = if is_json(matches) {
+            json_ops::to_pretty_json(&comment, SERIALIZE_ISSUE_COMMENT)?
+        } else {
+            comment.html_url
+        };
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    const EDITOR_TEMPLATE: &'static str = "\n# Write your comment above. This line is ignored.\n";
+    const NO_BODY: &'static str = "A comment needs a body. Pass --body, --body-file or --editor.";
+    const SERIALIZE_ISSUE_COMMENT: &'static str = "Error serializing GitHub Issue Comment JSON.";
+}
+
+#[cfg(test)]
+mod tests {
+use error::{ApiError, GhError};
+use hyper::status::StatusCode;
+use super::{explain_error, GONE, NOT_FOUND};
+
+    fn api_error(status: StatusCode) -> GhError {
+        GhError::Api(status, ApiError {
+            message: "Not Found".to_owned(),
+            documentation_url: None,
+            errors: vec![]
+        })
+    }
+
+    #[test]
+    fn test_explain_error_not_found() -> () {
+        assert_eq!(explain_error(api_error(StatusCode::NotFound)).to_string(), NOT_FOUND);
+    }
+
+    #[test]
+    fn test_explain_error_gone() -> () {
+        assert_eq!(explain_error(api_error(StatusCode::Gone)).to_string(), GONE);
+    }
+}
@@ -1,2 +1,291 @@
+pub mod issues;
 pub mod orgs;
 pub mod pullreqs;
+pub mod rate_limit;
+pub mod repos;
+pub mod teams;
+pub mod users;
+
+use clap::{Arg, ArgMatches};
+use config::load_config;
+use editor;
+use error::{GhError, GhResult};
+use git;
+use git_hub::Client;
+use git_hub::pagination::{Pagination, MAX_PER_PAGE};
+use hyper::status::StatusCode;
+
+use std::fs::File;
+use std::io;
+use std::io::{BufRead, Read, Write};
+
+#[cfg(windows)] pub const NL: &'static str = "\r\n";
+#[cfg(not(windows))] pub const NL: &'static str = "\n";
+
+// The --per-page, --page and --limit arguments shared by every list command.
+pub fn pagination_args<'a, 'b>() -> Vec<Arg<'a, 'b>> {
+    vec![
+        Arg::with_name("per-page")
+            .long("per-page")
+            .help("The number of results to request per page, at most 100.")
+            .value_name("N")
+            .takes_value(true)
+            .validator(is_page_size),
+        Arg::with_name("page")
+            .long("page")
+            .help("Fetches only this page of results instead of every page.")
+            .value_name("N")
+            .takes_value(true)
+            .validator(is_positive_number),
+        Arg::with_name("limit")
+            .long("limit")
+            .help("Stops after this many results.")
+            .value_name("N")
+            .takes_value(true)
+            .validator(is_positive_number)
+    ]
+}
+
+pub fn pagination_from_args(matches: &ArgMatches) -> Pagination {
+    Pagination {
+        per_page: matches.value_of("per-page").map(|x| x.parse().unwrap()),
+        page: matches.value_of("page").map(|x| x.parse().unwrap()),
+        limit: matches.value_of("limit").map(|x| x.parse().unwrap())
+    }
+}
+
+// The --format argument of every command with JSON output.
+pub fn format_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("format")
+        .short("f")
+        .long("format")
+        .help("Sets the output format.")
+        .value_name("json")
+        .takes_value(true)
+}
+
+pub fn is_json(matches: &ArgMatches) -> bool {
+    match matches.value_of("format") {
+        None         => false,
+        Some(format) => format == "json"
+    }
+}
+
+// Every value of a repeatable argument, none when it isn't given.
+pub fn values(matches: &ArgMatches, name: &str) -> Vec<String> {
+    match matches.values_of(name) {
+        Some(values) => values.map(|x| x.to_owned()).collect(),
+        None         => vec![]
+    }
+}
+
+// Replaces GitHub's message for a 401, 403 or 404 with one saying what to
+// check. What a 403 or 404 means depends on the command, so each command
+// passes its own `forbidden` and `not_found`.
+pub fn explain_error(error: GhError, forbidden: &str, not_found: &str) -> GhError {
+    match error {
+        GhError::Api(StatusCode::Forbidden, _)    => GhError::Auth(StatusCode::Forbidden, forbidden.to_owned()),
+        GhError::Api(StatusCode::Unauthorized, _) => GhError::Auth(StatusCode::Unauthorized, UNAUTHORIZED.to_owned()),
+        GhError::Api(StatusCode::NotFound, _)     => GhError::Http(StatusCode::NotFound, not_found.to_owned()),
+        error                                     => error
+    }
+}
+
+// A GitHub client for the credentials selected by --profile and --config,
+// waiting out rate limits when --wait-for-rate-limit is given.
+pub fn client_from_args(matches: &ArgMatches) -> GhResult<Client> {
+    Client::new(&load_config(matches)?, matches.is_present("wait-for-rate-limit"))
+}
+
+// Resolves the body of a pull request, issue or comment from --body,
+// --body-file, or --editor, in that order. `what` names it in error messages
+// and `editor_file` is the file name $EDITOR is opened on. No body is sent
+// when none of them are present.
+pub fn body_from_args(matches: &ArgMatches,
+                      what: &str,
+                      editor_file: &str,
+                      template: &str) -> GhResult<Option<String>> {
+    if let Some(body) = matches.value_of("body") {
+        Ok(Some(body.to_owned()))
+    } else if let Some(path) = matches.value_of("body-file") {
+        let mut contents = String::new();
+        let result = if path == "-" {
+            io::stdin().read_to_string(&mut contents)
+        } else {
+            File::open(path).and_then(|mut f| f.read_to_string(&mut contents))
+        };
+        match result {
+            Ok(_)  => Ok(Some(contents)),
+            Err(e) => Err(GhError::Io(format!("could not read {} body from {}", what, path), e))
+        }
+    } else if matches.is_present("editor") {
+        match editor::edit(editor_file, template) {
+            Ok(body) => Ok(Some(body)),
+            Err(e)   => Err(GhError::Io(format!("could not read {} body from editor", what), e))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+// Splits an OWNER/REPO argument into its owner and repository.
+pub fn split_full_name(full_name: &str) -> GhResult<(&str, &str)> {
+    let mut parts = full_name.splitn(2, '/');
+    match (parts.next(), parts.next()) {
+        (Some(owner), Some(repo)) if !owner.is_empty() && !repo.is_empty() && !repo.contains('/') => Ok((owner, repo)),
+        _ => Err(GhError::Usage(format!("{} is not a repository, expected OWNER/REPO", full_name)))
+    }
+}
+
+// The repository a repository scoped command works on: the `owner` and
+// `repository` arguments of the commands that take them, then the global
+// --repo OWNER/REPO, then the GitHub remote of the current git checkout on
+// the client's GitHub host.
+pub fn repo_from_args(matches: &ArgMatches, client: &Client) -> GhResult<(String, String)> {
+    if let (Some(owner), Some(repo)) = (matches.value_of("owner"), matches.value_of("repository")) {
+        return Ok((owner.to_owned(), repo.to_owned()));
+    }
+    if let Some(full_name) = matches.value_of("repo") {
+        let (owner, repo) = split_full_name(full_name)?;
+        return Ok((owner.to_owned(), repo.to_owned()));
+    }
+    match git::current_repo(&git::web_host(client.config().api_url())) {
+        Some(repo) => Ok(repo),
+        None       => Err(GhError::Usage(NO_REPOSITORY.to_owned()))
+    }
+}
+
+// The repository a destructive command works on, from its `full-name`
+// OWNER/REPO argument or the global --repo. It is never inferred from the
+// current git checkout, where upstream would win over a fork.
+pub fn explicit_repo_from_args(matches: &ArgMatches) -> GhResult<(String, String)> {
+    match matches.value_of("full-name").or(matches.value_of("repo")) {
+        Some(full_name) => {
+            let (owner, repo) = split_full_name(full_name)?;
+            Ok((owner.to_owned(), repo.to_owned()))
+        },
+        None => Err(GhError::Usage(NO_EXPLICIT_REPOSITORY.to_owned()))
+    }
+}
+
+// Asks the user to type `expected` to go ahead with something that can't
+// be undone. Anything else, or no answer at all, cancels.
+pub fn confirm(prompt: &str, expected: &str) -> GhResult<bool> {
+    print!("{} Type {} to confirm: ", prompt, expected);
+    io::stdout().flush().map_err(|e| GhError::Io("could not write the confirmation prompt".to_owned(), e))?;
+    let mut answer = String::new();
+    let stdin = io::stdin();
+    stdin.lock()
+         .read_line(&mut answer)
+         .map_err(|e| GhError::Io("could not read the confirmation".to_owned(), e))?;
+    Ok(answer.trim() == expected)
+}
+
+pub fn is_positive_number(value: String) -> Result<(), String> {
+    match value.parse::<u32>() {
+        Ok(x) if x > 0 => Ok(()),
+        _              => Err(format!("{} is not a positive number", value))
+    }
+}
+
+// GitHub silently caps larger page sizes at MAX_PER_PAGE.
+fn is_page_size(value: String) -> Result<(), String> {
+    match value.parse::<u32>() {
+        Ok(x) if x > 0 && x <= MAX_PER_PAGE => Ok(()),
+        _ => Err(format!("{} is not a number from 1 to {}", value, MAX_PER_PAGE))
+    }
+}
+
+pub const UNAUTHORIZED: &'static str = "401 Unauthorized. Bad Credentials. See https://developer.github.com/v3";
+
+const NO_REPOSITORY: &'static str = "No repository given. Pass --repo OWNER/REPO, or run gh inside a git checkout with a GitHub origin or upstream remote.";
+const NO_EXPLICIT_REPOSITORY: &'static str = "No repository given. Name it as OWNER/REPO or with --repo, it is never taken from the current git checkout.";
+
+#[cfg(test)]
+mod tests {
+use clap::{App, Arg};
+use error::{ApiError, GhError};
+use hyper::status::StatusCode;
+use super::{explain_error, explicit_repo_from_args, is_json, is_page_size, split_full_name, values, UNAUTHORIZED};
+
+    const FORBIDDEN: &'static str = "403 Forbidden. Does your OAuth token have sufficient scope?";
+    const NOT_FOUND: &'static str = "404 Not Found. Does the repository exist?";
+
+    fn api_error(status: StatusCode) -> GhError {
+        GhError::Api(status, ApiError {
+            message: "Not Found".to_owned(),
+            documentation_url: None,
+            errors: vec![]
+        })
+    }
+
+    #[test]
+    fn test_explain_error_forbidden() -> () {
+        let error = explain_error(api_error(StatusCode::Forbidden), FORBIDDEN, NOT_FOUND);
+        assert_eq!(error.to_string(), FORBIDDEN);
+        assert_eq!(error.exit_code(), 4);
+    }
+
+    #[test]
+    fn test_explain_error_unauthorized() -> () {
+        assert_eq!(explain_error(api_error(StatusCode::Unauthorized), FORBIDDEN, NOT_FOUND).to_string(), UNAUTHORIZED);
+    }
+
+    #[test]
+    fn test_explain_error_not_found() -> () {
+        let error = explain_error(api_error(StatusCode::NotFound), FORBIDDEN, NOT_FOUND);
+        assert_eq!(error.to_string(), NOT_FOUND);
+        assert_eq!(error.exit_code(), 5);
+    }
+
+    #[test]
+    fn test_explain_error_unknown() -> () {
+        assert_eq!(explain_error(api_error(StatusCode::ImATeapot), FORBIDDEN, NOT_FOUND).to_string(),
+                   "418 I'm a teapot: Not Found");
+    }
+
+    #[test]
+    fn test_is_json_and_values() -> () {
+        let app = || App::new("gh").arg(Arg::with_name("format").long("format").takes_value(true))
+                                   .arg(Arg::with_name("label").long("label").takes_value(true).multiple(true));
+        let matches = app().get_matches_from(vec!["gh", "--format", "json", "--label", "bug", "--label", "ui"]);
+        assert!(is_json(&matches));
+        assert_eq!(values(&matches, "label"), vec!["bug".to_owned(), "ui".to_owned()]);
+        let matches = app().get_matches_from(vec!["gh", "--format", "table"]);
+        assert!(!is_json(&matches));
+        assert!(values(&matches, "label").is_empty());
+    }
+
+    #[test]
+    fn test_split_full_name() -> () {
+        assert_eq!(split_full_name("octocat/Hello-World").unwrap(), ("octocat", "Hello-World"));
+    }
+
+    #[test]
+    fn test_split_full_name_invalid() -> () {
+        for full_name in &["octocat", "octocat/", "/Hello-World", "octocat/Hello-World/issues"] {
+            assert_eq!(split_full_name(full_name).unwrap_err().exit_code(), 2);
+        }
+    }
+
+    #[test]
+    fn test_explicit_repo_from_args() -> () {
+        let app = || App::new("gh").arg(Arg::with_name("repo").long("repo").takes_value(true))
+                                   .arg(Arg::with_name("full-name"));
+        let matches = app().get_matches_from(vec!["gh", "octocat/Hello-World"]);
+        assert_eq!(explicit_repo_from_args(&matches).unwrap(), ("octocat".to_owned(), "Hello-World".to_owned()));
+        let matches = app().get_matches_from(vec!["gh", "--repo", "hubot/Hello-World"]);
+        assert_eq!(explicit_repo_from_args(&matches).unwrap(), ("hubot".to_owned(), "Hello-World".to_owned()));
+        let matches = app().get_matches_from(vec!["gh"]);
+        assert_eq!(explicit_repo_from_args(&matches).unwrap_err().exit_code(), 2);
+    }
+
+    #[test]
+    fn test_is_page_size() -> () {
+        assert!(is_page_size("1".to_owned()).is_ok());
+        assert!(is_page_size("100".to_owned()).is_ok());
+        assert!(is_page_size("0".to_owned()).is_err());
+        assert!(is_page_size("101".to_owned()).is_err());
+        assert!(is_page_size("ten".to_owned()).is_err());
+    }
+}
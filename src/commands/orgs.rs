@@ -1,9 +1,6 @@
 use clap::{App, Arg, ArgMatches, SubCommand};
-use config;
-use git_hub::{GitHubResponse, orgs};
-
-use serde_json;
-use serde_json::Error;
+use commands;
+use error::{GhError, GhResult};
 
 pub fn SUBCOMMAND<'a, 'b>() -> App<'a, 'b> {
     SubCommand::with_name("orgs")
@@ -18,67 +15,222 @@ pub fn SUBCOMMAND<'a, 'b>() -> App<'a, 'b> {
                                              .help("Searches for public organizations for this user")
                                              .value_name("octocat")
                                              .takes_value(true))
-                                        .arg(Arg::with_name("format")
-                                             .short("f")
-                                             .long("format")
-                                             .help("Sets the output format.")
-                                             .value_name("json")
-                                             .takes_value(true)))
+                                        .arg(commands::format_arg())
+                                        .args(&commands::pagination_args()))
+                .subcommand(SubCommand::with_name("view")
+                                        .about("Shows an Organization's profile. Its plan and settings are only shown to owners.")
+                                        .arg(org_arg())
+                                        .arg(commands::format_arg()))
+                .subcommand(SubCommand::with_name("edit")
+                                        .about("Edits an Organization's profile and member privileges. Requires the admin:org scope.")
+                                        .arg(org_arg())
+                                        .arg(Arg::with_name("billing-email")
+                                             .long("billing-email")
+                                             .help("The billing email address. Never made public.")
+                                             .value_name("email")
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("description")
+                                             .short("d")
+                                             .long("description")
+                                             .help("A short description of the Organization.")
+                                             .value_name("text")
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("default-repo-permission")
+                                             .long("default-repo-permission")
+                                             .help("The permission members have on the Organization's repositories.")
+                                             .possible_values(&["read", "write", "admin", "none"])
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("members-can-create-repos")
+                                             .long("members-can-create-repos")
+                                             .help("Whether members can create repositories.")
+                                             .possible_values(&["true", "false"])
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("members-can-create-public-repos")
+                                             .long("members-can-create-public-repos")
+                                             .help("Whether members can create public repositories.")
+                                             .possible_values(&["true", "false"])
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("members-can-create-private-repos")
+                                             .long("members-can-create-private-repos")
+                                             .help("Whether members can create private repositories.")
+                                             .possible_values(&["true", "false"])
+                                             .takes_value(true))
+                                        .arg(commands::format_arg()))
+                .subcommand(SubCommand::with_name("members")
+                                        .about("Lists, adds and removes the members of an Organization, and sets their role.")
+                                        .subcommand(SubCommand::with_name("list")
+                                                                .about("Lists the members of an Organization.")
+                                                                .arg(org_arg())
+                                                                .arg(Arg::with_name("role")
+                                                                     .short("r")
+                                                                     .long("role")
+                                                                     .help("Filters members by role.")
+                                                                     .possible_values(&["all", "admin", "member"])
+                                                                     .takes_value(true))
+                                                                .arg(two_factor_filter_arg())
+                                                                .arg(commands::format_arg())
+                                                                .args(&commands::pagination_args()))
+                                        .subcommand(SubCommand::with_name("add")
+                                                                .about("Invites a user to an Organization.")
+                                                                .arg(org_arg())
+                                                                .arg(username_arg())
+                                                                .arg(Arg::with_name("role")
+                                                                     .short("r")
+                                                                     .long("role")
+                                                                     .help("The role to invite the user with. Defaults to an existing member's role, or member.")
+                                                                     .possible_values(&["admin", "member"])
+                                                                     .takes_value(true))
+                                                                .arg(commands::format_arg()))
+                                        .subcommand(SubCommand::with_name("remove")
+                                                                .about("Removes a user from an Organization and all of its teams.")
+                                                                .arg(org_arg())
+                                                                .arg(username_arg())
+                                                                .arg(Arg::with_name("yes")
+                                                                     .short("y")
+                                                                     .long("yes")
+                                                                     .help("Removes without asking for confirmation.")))
+                                        .subcommand(SubCommand::with_name("role")
+                                                                .about("Shows a member's role, or changes it.")
+                                                                .arg(org_arg())
+                                                                .arg(username_arg())
+                                                                .arg(Arg::with_name("role")
+                                                                     .possible_values(&["admin", "member"])
+                                                                     .help("The new role."))
+                                                                .arg(commands::format_arg())))
+                .subcommand(commands::teams::SUBCOMMAND())
+                .subcommand(SubCommand::with_name("invitations")
+                                        .about("Lists, sends and cancels invitations to join an Organization.")
+                                        .subcommand(SubCommand::with_name("list")
+                                                                .about("Lists an Organization's pending invitations.")
+                                                                .arg(org_arg())
+                                                                .arg(commands::format_arg())
+                                                                .args(&commands::pagination_args()))
+                                        .subcommand(SubCommand::with_name("create")
+                                                                .about("Invites a GitHub user, or anyone by email, to join an Organization.")
+                                                                .arg(org_arg())
+                                                                .arg(Arg::with_name("invitee")
+                                                                     .required(true)
+                                                                     .value_name("login|email")
+                                                                     .help("The login of the user to invite, or an email address."))
+                                                                .arg(Arg::with_name("role")
+                                                                     .short("r")
+                                                                     .long("role")
+                                                                     .help("The role to invite with. Defaults to direct_member.")
+                                                                     .possible_values(&["admin", "direct_member", "billing_manager"])
+                                                                     .takes_value(true))
+                                                                .arg(Arg::with_name("team-id")
+                                                                     .short("t")
+                                                                     .long("team-id")
+                                                                     .help("Adds the invitee to this team once they accept. May be repeated.")
+                                                                     .value_name("id")
+                                                                     .takes_value(true)
+                                                                     .multiple(true)
+                                                                     .number_of_values(1)
+                                                                     .validator(commands::is_positive_number))
+                                                                .arg(commands::format_arg()))
+                                        .subcommand(SubCommand::with_name("cancel")
+                                                                .about("Cancels a pending invitation.")
+                                                                .arg(org_arg())
+                                                                .arg(Arg::with_name("id")
+                                                                     .required(true)
+                                                                     .help("The id of the invitation, see gh orgs invitations list.")
+                                                                     .validator(commands::is_positive_number))))
+                .subcommand(SubCommand::with_name("outside-collaborators")
+                                        .about("Lists, converts and removes the collaborators on an Organization's repositories who aren't members of it.")
+                                        .subcommand(SubCommand::with_name("list")
+                                                                .about("Lists an Organization's outside collaborators.")
+                                                                .arg(org_arg())
+                                                                .arg(two_factor_filter_arg())
+                                                                .arg(commands::format_arg())
+                                                                .args(&commands::pagination_args()))
+                                        .subcommand(SubCommand::with_name("convert")
+                                                                .about("Turns a member into an outside collaborator.")
+                                                                .arg(org_arg())
+                                                                .arg(username_arg()))
+                                        .subcommand(SubCommand::with_name("remove")
+                                                                .about("Removes an outside collaborator from every repository of an Organization.")
+                                                                .arg(org_arg())
+                                                                .arg(username_arg())))
+                .subcommand(SubCommand::with_name("2fa-report")
+                                        .about("Lists the members and outside collaborators of an Organization without two-factor authentication. Owners only.")
+                                        .arg(org_arg())
+                                        .arg(commands::format_arg()))
 }
 
-pub fn handle(matches: &ArgMatches) -> () {
+pub fn handle(matches: &ArgMatches) -> GhResult<()> {
     match matches.subcommand() {
         ("list", Some(list_matches)) => list::handle(list_matches),
-        ("", None)                   => println!("No subcommand was used for orgs"),
+        ("view", Some(view_matches)) => view::handle(view_matches),
+        ("edit", Some(edit_matches)) => edit::handle(edit_matches),
+        ("members", Some(members_matches)) => members::handle(members_matches),
+        ("teams", Some(teams_matches)) => commands::teams::handle(teams_matches),
+        ("invitations", Some(invitations_matches)) => invitations::handle(invitations_matches),
+        ("outside-collaborators", Some(collaborators_matches)) => outside_collaborators::handle(collaborators_matches),
+        ("2fa-report", Some(report_matches)) => two_factor_report::handle(report_matches),
+        ("", None)                   => {
+            println!("No subcommand was used for orgs");
+            Ok(())
+        },
         (_, _)                       => unreachable!()
     }
 }
 
+fn org_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("org")
+        .required(true)
+        .help("The login of the Organization.")
+}
+
+fn username_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("username")
+        .required(true)
+        .help("The login of the user.")
+}
+
+fn two_factor_filter_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("filter")
+        .long("filter")
+        .help("2fa_disabled lists only those without two-factor authentication. Owners only.")
+        .possible_values(&["all", "2fa_disabled"])
+        .takes_value(true)
+}
+
+fn explain_error(error: GhError) -> GhError {
+    commands::explain_error(error, FORBIDDEN, NOT_FOUND)
+}
+
+const FORBIDDEN: &'static str = "403 Forbidden. Changing an Organization needs the admin:org scope and an owner's credentials. See https://developer.github.com/v3/orgs/";
+const NOT_FOUND: &'static str = "404 Not Found. Does the Organization or user exist, and can your credentials see them? See https://developer.github.com/v3/orgs/";
+
 mod list {
 use clap::ArgMatches;
-use config::load_config;
+use commands;
+use commands::{client_from_args, is_json, NL, pagination_from_args};
+use error::{GhError, GhResult};
 use evidence::json_ops;
-use git_hub::{GitHubResponse, orgs};
-use hyper::status::StatusCode;
+use git_hub::orgs;
 use git_hub::orgs::OrgSummary;
 
-    #[cfg(windows)] pub const NL: &'static str = "\r\n";
-    #[cfg(not(windows))] pub const NL: &'static str = "\n";
-
-    pub fn handle(matches: &ArgMatches) -> () {
-        let response = match matches.value_of("user") {
-            None       => orgs::get_authed_user_orgs(&load_config()),
-            Some(user) => orgs::get_user_public_orgs(user, &load_config()),
-        };
-        let is_json  = match matches.value_of("format") {
-            None         => false,
-            Some(format) => format == "json"
-        };
-        let output = &build_output(&response, is_json);
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let pagination = pagination_from_args(matches);
+        let client = client_from_args(matches)?;
+        let orgs = match matches.value_of("user") {
+            None       => orgs::get_authed_user_orgs(&client, &pagination),
+            Some(user) => orgs::get_user_public_orgs(user, &client, &pagination),
+        }.map_err(explain_error)?;
+        let output = &format_output(orgs, is_json(matches))?;
         println!("{}", output.trim());
+        Ok(())
     }
 
-    fn build_output(response: &GitHubResponse, is_json: bool) -> String {
-        match response.status {
-            StatusCode::Forbidden    => FORBIDDEN.to_owned(),
-            StatusCode::Unauthorized => UNAUTHORIZED.to_owned(),
-            StatusCode::Ok           => match response.body {
-                None           => build_200_ok_no_string_body_output(),
-                Some(ref body) => format_output(body.to_owned(), is_json),
-            },
-            x                        => format!("Unexpected Http Response Code {}", x)
-        }
-    }
-
-    fn build_200_ok_no_string_body_output() -> String {
-        format!("An unknown error occurred. GitHub responded with {}, but no string body was found.",
-                StatusCode::Ok)
+    // Listing needs less than the other orgs commands, so a 403 says so
+    fn explain_error(error: GhError) -> GhError {
+        commands::explain_error(error, FORBIDDEN, super::NOT_FOUND)
     }
 
-    fn format_output(body: String, is_json: bool) -> String {
-        let orgs: Vec<OrgSummary> = json_ops::from_str_or_die(&body, DESERIALIZE_ORG_SUMMARY);
+    fn format_output(orgs: Vec<OrgSummary>, is_json: bool) -> GhResult<String> {
         if is_json {
-            json_ops::to_pretty_json_or_die(&orgs, SERIALIZE_ORG_SUMMARY)
+            json_ops::to_pretty_json(&orgs, SERIALIZE_ORG_SUMMARY)
         } else {
             let mut output = String::with_capacity(100);
             let header = format!("{0: <10} {1: <10} {2: <45} {3: <30}", "login", "id", "url", "description");
@@ -86,78 +238,577 @@ use git_hub::orgs::OrgSummary;
             output.push_str(NL);
             for org in orgs {
                 let line = format!("{0: <10} {1: <10} {2: <45} {3: <30}",
-                                   org.login, org.id, org.url, org.description);
+                                   org.login, org.id, org.url, org.description.unwrap_or(String::new()));
                 output.push_str(&line);
                 output.push_str(NL);
             }
-            output
+            Ok(output)
         }
     }
 
-    const DESERIALIZE_ORG_SUMMARY: &'static str = "Error deserializing GitHub Organization Summary JSON.";
     const SERIALIZE_ORG_SUMMARY: &'static str = "Error serializing GitHub Organization Summary JSON.";
 
-    const UNAUTHORIZED: &'static str = "401 Unauthorized. Bad Credentials. See https://developer.github.com/v3";
-    const FORBIDDEN: &'static str = "403 Forbidden. Does your OAuth token have suffecient scope? A minimum of `user` or `read:org` is required. See https://developer.github.com/v3/orgs/";
+    const FORBIDDEN: &'static str = "403 Forbidden. Does your OAuth token have sufficient scope? A minimum of `user` or `read:org` is required. See https://developer.github.com/v3/orgs/";
 
     #[cfg(test)]
     mod tests {
-    use git_hub::GitHubResponse;
-    use hyper::header::Headers;
+    use commands::UNAUTHORIZED;
+    use error::{ApiError, GhError};
+    use git_hub::orgs::OrgSummary;
     use hyper::status::StatusCode;
-    use super::{build_output, FORBIDDEN, UNAUTHORIZED};
+    use super::{explain_error, format_output, FORBIDDEN};
+
+        fn api_error(status: StatusCode) -> GhError {
+            GhError::Api(status, ApiError {
+                message: "Bad credentials".to_owned(),
+                documentation_url: None,
+                errors: vec![]
+            })
+        }
 
         #[test]
-        fn test_build_output_forbidden() -> () {
-            let response = GitHubResponse {
-                status: StatusCode::Forbidden,
-                headers: Headers::new(),
-                body: None
-            };
-            assert_eq!(build_output(&response, false), FORBIDDEN);
+        fn test_explain_error_forbidden() -> () {
+            let error = explain_error(api_error(StatusCode::Forbidden));
+            assert_eq!(error.to_string(), FORBIDDEN);
+            assert_eq!(error.exit_code(), 4);
         }
 
         #[test]
-        fn test_build_output_unauthorized() -> () {
-            let response = GitHubResponse {
-                status: StatusCode::Unauthorized,
-                headers: Headers::new(),
-                body: None
-            };
-            assert_eq!(build_output(&response, false), UNAUTHORIZED);
+        fn test_explain_error_unauthorized() -> () {
+            assert_eq!(explain_error(api_error(StatusCode::Unauthorized)).to_string(), UNAUTHORIZED);
+        }
+
+        #[test]
+        fn test_explain_error_unknown() -> () {
+            assert_eq!(explain_error(api_error(StatusCode::ImATeapot)).to_string(),
+                       "418 I'm a teapot: Bad credentials");
         }
 
         #[test]
-        fn test_build_output_unknown() -> () {
-            let response = GitHubResponse {
-                status: StatusCode::ImATeapot,
-                headers: Headers::new(),
-                body: None
+        fn test_format_output() -> () {
+            let org = OrgSummary {
+                login: "github".to_owned(),
+                id: 1,
+                url: "https://api.github.com/orgs/github".to_owned(),
+                repos_url: "".to_owned(),
+                events_url: "".to_owned(),
+                hooks_url: "".to_owned(),
+                issues_url: "".to_owned(),
+                members_url: "".to_owned(),
+                public_members_url: "".to_owned(),
+                avatar_url: "".to_owned(),
+                description: Some("A great organization".to_owned())
             };
-            assert_eq!(build_output(&response, false),
-                       "Unexpected Http Response Code 418 I'm a teapot");
+            let output = format_output(vec![org], false).unwrap();
+            let lines: Vec<&str> = output.trim().lines().collect();
+            assert_eq!(lines.len(), 2);
+            assert!(lines[1].starts_with("github     1          https://api.github.com/orgs/github"));
         }
+    }
+}
+
+mod view {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, NL};
+use error::GhResult;
+use evidence::json_ops;
+use git_hub::orgs;
+use git_hub::orgs::Organization;
+use super::explain_error;
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let org = orgs::get_org(matches.value_of("org").unwrap(), &client_from_args(matches)?)
+                       .map_err(explain_error)?;
+        let output = &format_output(org, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    fn yes_no(value: bool) -> &'static str {
+        if value { "yes" } else { "no" }
+    }
+
+    // Fields only owners can see are left out when GitHub doesn't send them
+    fn format_output(org: Organization, is_json: bool) -> GhResult<String> {
+        if is_json {
+            return json_ops::to_pretty_json(&org, SERIALIZE_ORGANIZATION);
+        }
+        let mut fields: Vec<(&str, String)> = vec![];
+        fields.push(("description", org.description.clone().unwrap_or(String::new())));
+        fields.push(("url", org.html_url.clone()));
+        for &(name, ref value) in &[("blog", &org.blog), ("location", &org.location), ("email", &org.email)] {
+            if let Some(ref value) = **value {
+                fields.push((name, value.clone()));
+            }
+        }
+        fields.push(("verified", yes_no(org.is_verified).to_owned()));
+        fields.push(("public repos", org.public_repos.to_string()));
+        if let Some(total) = org.total_private_repos {
+            fields.push(("private repos", total.to_string()));
+        }
+        fields.push(("followers", org.followers.to_string()));
+        if let Some(ref plan) = org.plan {
+            let seats = match (plan.filled_seats, plan.seats) {
+                (Some(filled), Some(seats)) => format!(", {} of {} seats filled", filled, seats),
+                _                           => String::new()
+            };
+            fields.push(("plan", format!("{}{}", plan.name, seats)));
+        }
+        if let Some(ref billing_email) = org.billing_email {
+            fields.push(("billing email", billing_email.clone()));
+        }
+        if let Some(ref permission) = org.default_repository_permission {
+            fields.push(("default repo permission", permission.clone()));
+        }
+        if let Some(can_create) = org.members_can_create_repositories {
+            fields.push(("members can create repos", yes_no(can_create).to_owned()));
+        }
+        if let Some(required) = org.two_factor_requirement_enabled {
+            fields.push(("2fa required", yes_no(required).to_owned()));
+        }
+        fields.push(("created", org.created_at.clone()));
+
+        let mut output = String::with_capacity(500);
+        match org.name {
+            Some(ref name) => output.push_str(&format!("{} ({})", org.login, name)),
+            None           => output.push_str(&org.login)
+        }
+        output.push_str(NL);
+        for (name, value) in fields {
+            output.push_str(&format!("{0: <25} {1}", name, value));
+            output.push_str(NL);
+        }
+        Ok(output)
+    }
+
+    const SERIALIZE_ORGANIZATION: &'static str = "Error serializing GitHub Organization JSON.";
+
+    #[cfg(test)]
+    mod tests {
+    use evidence::json_ops;
+    use super::format_output;
 
         #[test]
-        fn test_build_200_ok_no_string_body_output() -> () {
-            assert_eq!(super::build_200_ok_no_string_body_output(),
-                      "An unknown error occurred. GitHub responded with 200 OK, but no string body was found.");
+        fn test_format_output_owner() -> () {
+            let body = "{\"login\":\"github\",\"id\":1,\"url\":\"https://api.github.com/orgs/github\",\
+                        \"html_url\":\"https://github.com/github\",\"repos_url\":\"\",\"members_url\":\"\",\
+                        \"public_members_url\":\"\",\"avatar_url\":\"\",\"description\":\"A great organization\",\
+                        \"name\":\"GitHub\",\"company\":null,\"blog\":\"https://github.com/blog\",\"location\":null,\
+                        \"email\":null,\"is_verified\":true,\"public_repos\":2,\"public_gists\":1,\"followers\":20,\
+                        \"following\":0,\"created_at\":\"2008-01-14T04:33:35Z\",\"updated_at\":\"2014-03-03T18:58:10Z\",\
+                        \"total_private_repos\":100,\"owned_private_repos\":100,\"billing_email\":\"mona@github.com\",\
+                        \"plan\":{\"name\":\"Medium\",\"space\":400,\"private_repos\":20,\"filled_seats\":4,\"seats\":5},\
+                        \"default_repository_permission\":\"read\",\"members_can_create_repositories\":true,\
+                        \"two_factor_requirement_enabled\":true}";
+            let output = format_output(json_ops::from_str(body, "").unwrap(), false).unwrap();
+            let lines: Vec<&str> = output.trim().lines().collect();
+            assert_eq!(lines[0], "github (GitHub)");
+            assert_eq!(lines[3], "blog                      https://github.com/blog");
+            assert_eq!(lines[8], "plan                      Medium, 4 of 5 seats filled");
+            assert_eq!(lines[12], "2fa required              yes");
         }
 
         #[test]
-        fn test_build_output_no_string_body() -> () {
-            let response = GitHubResponse {
-                status: StatusCode::Ok,
-                headers: Headers::new(),
-                body: None
-            };
-            assert_eq!(build_output(&response, false),
-                      "An unknown error occurred. GitHub responded with 200 OK, but no string body was found.");
+        fn test_format_output_public() -> () {
+            let body = "{\"login\":\"github\",\"id\":1,\"url\":\"\",\"html_url\":\"https://github.com/github\",\
+                        \"repos_url\":\"\",\"members_url\":\"\",\"public_members_url\":\"\",\"avatar_url\":\"\",\
+                        \"description\":null,\"name\":null,\"created_at\":\"2008-01-14T04:33:35Z\",\
+                        \"updated_at\":\"2014-03-03T18:58:10Z\"}";
+            let output = format_output(json_ops::from_str(body, "").unwrap(), false).unwrap();
+            assert!(output.starts_with("github\n"));
+            assert!(!output.contains("plan"));
+        }
+    }
+}
+
+mod edit {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json};
+use error::{GhError, GhResult};
+use evidence::json_ops;
+use git_hub::orgs;
+use git_hub::orgs::EditOrganization;
+use super::explain_error;
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let flag = |name| matches.value_of(name).map(|x| x == "true");
+        let edit = EditOrganization {
+            billing_email: matches.value_of("billing-email").map(|x| x.to_owned()),
+            description: matches.value_of("description").map(|x| x.to_owned()),
+            default_repository_permission: matches.value_of("default-repo-permission").map(|x| x.to_owned()),
+            members_can_create_repositories: flag("members-can-create-repos"),
+            members_can_create_public_repositories: flag("members-can-create-public-repos"),
+            members_can_create_private_repositories: flag("members-can-create-private-repos")
+        };
+        let body = json_ops::to_json(&edit, SERIALIZE_EDIT_ORGANIZATION)?;
+        if body == "{}" {
+            return Err(GhError::Usage(NOTHING_TO_EDIT.to_owned()));
+        }
+        let org = orgs::edit_org(matches.value_of("org").unwrap(), &edit, &client_from_args(matches)?)
+                       .map_err(explain_error)?;
+        if is_json(matches) {
+            println!("{}", json_ops::to_pretty_json(&org, SERIALIZE_ORGANIZATION)?.trim());
+        } else {
+            println!("{0: <10} {1}", org.login, org.html_url);
+        }
+        Ok(())
+    }
+
+    const NOTHING_TO_EDIT: &'static str = "Nothing to edit. Pass at least one change, see gh orgs edit --help.";
+
+    const SERIALIZE_EDIT_ORGANIZATION: &'static str = "Error serializing GitHub Organization edit JSON.";
+    const SERIALIZE_ORGANIZATION: &'static str = "Error serializing GitHub Organization JSON.";
+}
+
+mod members {
+use clap::ArgMatches;
+use commands::{client_from_args, confirm, is_json, NL, pagination_from_args};
+use error::{GhError, GhResult};
+use evidence::json_ops;
+use git_hub::orgs;
+use git_hub::orgs::{MemberFilters, OrgMember, OrgMembership};
+use hyper::status::StatusCode;
+use super::explain_error;
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        match matches.subcommand() {
+            ("list", Some(list_matches))     => list(list_matches),
+            ("add", Some(add_matches))       => add(add_matches),
+            ("remove", Some(remove_matches)) => remove(remove_matches),
+            ("role", Some(role_matches))     => role(role_matches),
+            ("", None)                       => {
+                println!("No subcommand was used for orgs members");
+                Ok(())
+            },
+            (_, _)                           => unreachable!()
+        }
+    }
+
+    fn list(matches: &ArgMatches) -> GhResult<()> {
+        let filters = MemberFilters {
+            filter: matches.value_of("filter").map(|x| x.to_owned()),
+            role: matches.value_of("role").map(|x| x.to_owned())
+        };
+        let pagination = pagination_from_args(matches);
+        let members = orgs::list_members(matches.value_of("org").unwrap(), &filters, &client_from_args(matches)?, &pagination)
+                           .map_err(explain_error)?;
+        let output = &format_members(members, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    fn add(matches: &ArgMatches) -> GhResult<()> {
+        let org = matches.value_of("org").unwrap();
+        let username = matches.value_of("username").unwrap();
+        let client = client_from_args(matches)?;
+        // Without --role an existing member keeps their role, as setting
+        // member would demote an admin
+        let role = match matches.value_of("role") {
+            Some(role) => role.to_owned(),
+            None       => match orgs::get_membership(org, username, &client) {
+                Ok(membership)                             => membership.role,
+                Err(GhError::Api(StatusCode::NotFound, _)) => "member".to_owned(),
+                Err(e)                                     => return Err(explain_error(e))
+            }
+        };
+        let membership = orgs::set_membership(org, username, &role, &client).map_err(explain_error)?;
+        let output = &format_membership(membership, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    fn remove(matches: &ArgMatches) -> GhResult<()> {
+        let org = matches.value_of("org").unwrap();
+        let username = matches.value_of("username").unwrap();
+        if !matches.is_present("yes") {
+            let prompt = format!("This removes {} from {} and all of its teams.", username, org);
+            if !confirm(&prompt, username)? {
+                return Err(GhError::Usage(CANCELLED.to_owned()));
+            }
+        }
+        orgs::remove_member(org, username, &client_from_args(matches)?).map_err(explain_error)?;
+        println!("Removed {} from {}", username, org);
+        Ok(())
+    }
 
+    fn role(matches: &ArgMatches) -> GhResult<()> {
+        let org = matches.value_of("org").unwrap();
+        let username = matches.value_of("username").unwrap();
+        let client = client_from_args(matches)?;
+        let membership = match matches.value_of("role") {
+            Some(role) => orgs::set_membership(org, username, role, &client),
+            None       => orgs::get_membership(org, username, &client)
+        }.map_err(explain_error)?;
+        let output = &format_membership(membership, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    pub fn format_members(members: Vec<OrgMember>, is_json: bool) -> GhResult<String> {
+        if is_json {
+            return json_ops::to_pretty_json(&members, SERIALIZE_MEMBERS);
+        }
+        let mut output = String::with_capacity(100);
+        output.push_str(&format!("{0: <25} {1}", "login", "id"));
+        output.push_str(NL);
+        for member in members {
+            output.push_str(&format!("{0: <25} {1}", member.login, member.id));
+            output.push_str(NL);
+        }
+        Ok(output)
+    }
+
+    fn format_membership(membership: OrgMembership, is_json: bool) -> GhResult<String> {
+        if is_json {
+            json_ops::to_pretty_json(&membership, SERIALIZE_MEMBERSHIP)
+        } else {
+            Ok(format!("{0: <25} {1: <8} {2}", membership.user.login, membership.role, membership.state))
+        }
+    }
+
+    const SERIALIZE_MEMBERS: &'static str = "Error serializing GitHub Organization members JSON.";
+    const SERIALIZE_MEMBERSHIP: &'static str = "Error serializing GitHub Organization membership JSON.";
+    const CANCELLED: &'static str = "Removal cancelled, the confirmation did not match.";
+
+    #[cfg(test)]
+    mod tests {
+    use evidence::json_ops;
+    use super::format_membership;
+
+        #[test]
+        fn test_format_membership_pending() -> () {
+            let body = "{\"state\":\"pending\",\"role\":\"admin\",\
+                        \"organization_url\":\"https://api.github.com/orgs/octocat\",\
+                        \"user\":{\"login\":\"defunkt\",\"id\":3,\"site_admin\":false}}";
+            assert_eq!(format_membership(json_ops::from_str(body, "").unwrap(), false).unwrap(),
+                       "defunkt                   admin    pending");
         }
     }
 }
 
-// get finagle
-// patch finagle
-// list
+mod invitations {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, NL, pagination_from_args};
+use error::GhResult;
+use evidence::json_ops;
+use git_hub::{orgs, users};
+use git_hub::orgs::{NewOrgInvitation, OrgInvitation};
+use super::explain_error;
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        match matches.subcommand() {
+            ("list", Some(list_matches))     => list(list_matches),
+            ("create", Some(create_matches)) => create(create_matches),
+            ("cancel", Some(cancel_matches)) => cancel(cancel_matches),
+            ("", None)                       => {
+                println!("No subcommand was used for orgs invitations");
+                Ok(())
+            },
+            (_, _)                           => unreachable!()
+        }
+    }
+
+    fn list(matches: &ArgMatches) -> GhResult<()> {
+        let pagination = pagination_from_args(matches);
+        let invitations = orgs::list_invitations(matches.value_of("org").unwrap(), &client_from_args(matches)?, &pagination)
+                               .map_err(explain_error)?;
+        let output = &format_output(invitations, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    fn create(matches: &ArgMatches) -> GhResult<()> {
+        let client = client_from_args(matches)?;
+        let invitee = matches.value_of("invitee").unwrap();
+        // GitHub invites users by their id, so the login is looked up first
+        let mut invitation = NewOrgInvitation {
+            role: matches.value_of("role").map(|x| x.to_owned()),
+            team_ids: match matches.values_of("team-id") {
+                Some(ids) => ids.map(|x| x.parse().unwrap()).collect(),
+                None      => vec![]
+            },
+            ..Default::default()
+        };
+        if invitee.contains('@') {
+            invitation.email = Some(invitee.to_owned());
+        } else {
+            invitation.invitee_id = Some(users::get_user(invitee, &client).map_err(explain_error)?.id);
+        }
+        let created = orgs::create_invitation(matches.value_of("org").unwrap(), &invitation, &client)
+                           .map_err(explain_error)?;
+        let output = &format_output(vec![created], is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    fn cancel(matches: &ArgMatches) -> GhResult<()> {
+        let id = matches.value_of("id").unwrap();
+        orgs::cancel_invitation(matches.value_of("org").unwrap(), id.parse().unwrap(), &client_from_args(matches)?)
+             .map_err(explain_error)?;
+        println!("Cancelled invitation {}", id);
+        Ok(())
+    }
+
+    fn format_output(invitations: Vec<OrgInvitation>, is_json: bool) -> GhResult<String> {
+        if is_json {
+            return json_ops::to_pretty_json(&invitations, SERIALIZE_INVITATIONS);
+        }
+        let mut output = String::with_capacity(100);
+        output.push_str(&format!("{0: <10} {1: <30} {2: <16} {3: <20} {4}", "id", "invitee", "role", "inviter", "created"));
+        output.push_str(NL);
+        for invitation in invitations {
+            let invitee = invitation.login.or(invitation.email).unwrap_or(String::new());
+            let line = format!("{0: <10} {1: <30} {2: <16} {3: <20} {4}",
+                               invitation.id, invitee, invitation.role, invitation.inviter.login, invitation.created_at);
+            output.push_str(&line);
+            output.push_str(NL);
+        }
+        Ok(output)
+    }
+
+    const SERIALIZE_INVITATIONS: &'static str = "Error serializing GitHub Organization invitations JSON.";
+
+    #[cfg(test)]
+    mod tests {
+    use evidence::json_ops;
+    use super::format_output;
+
+        #[test]
+        fn test_format_output() -> () {
+            let body = "[{\"id\":1,\"login\":\"monalisa\",\"email\":\"octocat@github.com\",\"role\":\"direct_member\",\
+                        \"created_at\":\"2016-11-30T06:46:10-08:00\",\"inviter\":{\"login\":\"octocat\",\"id\":1},\
+                        \"team_count\":2},\
+                        {\"id\":2,\"login\":null,\"email\":\"hubot@example.com\",\"role\":\"admin\",\
+                        \"created_at\":\"2016-11-30T06:46:10-08:00\",\"inviter\":{\"login\":\"octocat\",\"id\":1}}]";
+            let output = format_output(json_ops::from_str(body, "").unwrap(), false).unwrap();
+            let lines: Vec<&str> = output.trim().lines().collect();
+            assert_eq!(lines[1], "1          monalisa                       direct_member    octocat              2016-11-30T06:46:10-08:00");
+            assert_eq!(lines[2], "2          hubot@example.com              admin            octocat              2016-11-30T06:46:10-08:00");
+        }
+    }
+}
+
+mod outside_collaborators {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, pagination_from_args};
+use error::GhResult;
+use git_hub::orgs;
+use super::explain_error;
+use super::members::format_members;
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        match matches.subcommand() {
+            ("list", Some(list_matches))       => list(list_matches),
+            ("convert", Some(convert_matches)) => convert(convert_matches),
+            ("remove", Some(remove_matches))   => remove(remove_matches),
+            ("", None)                         => {
+                println!("No subcommand was used for orgs outside-collaborators");
+                Ok(())
+            },
+            (_, _)                             => unreachable!()
+        }
+    }
+
+    fn list(matches: &ArgMatches) -> GhResult<()> {
+        let pagination = pagination_from_args(matches);
+        let collaborators = orgs::list_outside_collaborators(matches.value_of("org").unwrap(),
+                                                             matches.value_of("filter"),
+                                                             &client_from_args(matches)?,
+                                                             &pagination).map_err(explain_error)?;
+        let output = &format_members(collaborators, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    fn convert(matches: &ArgMatches) -> GhResult<()> {
+        let org = matches.value_of("org").unwrap();
+        let username = matches.value_of("username").unwrap();
+        orgs::convert_to_outside_collaborator(org, username, &client_from_args(matches)?).map_err(explain_error)?;
+        println!("{} is now an outside collaborator of {}", username, org);
+        Ok(())
+    }
+
+    fn remove(matches: &ArgMatches) -> GhResult<()> {
+        let org = matches.value_of("org").unwrap();
+        let username = matches.value_of("username").unwrap();
+        orgs::remove_outside_collaborator(org, username, &client_from_args(matches)?).map_err(explain_error)?;
+        println!("Removed {} from {}", username, org);
+        Ok(())
+    }
+}
+
+mod two_factor_report {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, NL};
+use error::GhResult;
+use evidence::json_ops;
+use git_hub::orgs;
+use git_hub::orgs::{MemberFilters, OrgMember};
+use git_hub::pagination::Pagination;
+use super::explain_error;
+
+    // The accounts of an Organization without two-factor authentication
+    #[derive(Serialize)]
+    struct TwoFactorReport {
+        members: Vec<OrgMember>,
+        outside_collaborators: Vec<OrgMember>
+    }
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let org = matches.value_of("org").unwrap();
+        let client = client_from_args(matches)?;
+        let pagination = Pagination::default();
+        let filters = MemberFilters {
+            filter: Some(TWO_FACTOR_DISABLED.to_owned()),
+            role: None
+        };
+        let report = TwoFactorReport {
+            members: orgs::list_members(org, &filters, &client, &pagination).map_err(explain_error)?,
+            outside_collaborators: orgs::list_outside_collaborators(org, Some(TWO_FACTOR_DISABLED), &client, &pagination)
+                                        .map_err(explain_error)?
+        };
+        let output = &format_output(report, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    fn format_output(report: TwoFactorReport, is_json: bool) -> GhResult<String> {
+        if is_json {
+            return json_ops::to_pretty_json(&report, SERIALIZE_REPORT);
+        }
+        let mut output = String::with_capacity(100);
+        output.push_str(&format!("{0: <25} {1}", "login", "affiliation"));
+        output.push_str(NL);
+        for member in report.members {
+            output.push_str(&format!("{0: <25} {1}", member.login, "member"));
+            output.push_str(NL);
+        }
+        for collaborator in report.outside_collaborators {
+            output.push_str(&format!("{0: <25} {1}", collaborator.login, "outside collaborator"));
+            output.push_str(NL);
+        }
+        Ok(output)
+    }
+
+    const TWO_FACTOR_DISABLED: &'static str = "2fa_disabled";
+
+    const SERIALIZE_REPORT: &'static str = "Error serializing two-factor authentication report JSON.";
+
+    #[cfg(test)]
+    mod tests {
+    use evidence::json_ops;
+    use super::{format_output, TwoFactorReport};
+
+        #[test]
+        fn test_format_output() -> () {
+            let report = TwoFactorReport {
+                members: json_ops::from_str("[{\"login\":\"octocat\",\"id\":1}]", "").unwrap(),
+                outside_collaborators: json_ops::from_str("[{\"login\":\"hubot\",\"id\":2}]", "").unwrap()
+            };
+            assert_eq!(format_output(report, false).unwrap().lines().collect::<Vec<&str>>(),
+                       vec!["login                     affiliation",
+                            "octocat                   member",
+                            "hubot                     outside collaborator"]);
+        }
+    }
+}
@@ -1,7 +1,7 @@
-use clap::{App, Arg, ArgMatches, SubCommand};
-use config;
-
-use serde_json;
+use clap::{App, Arg, ArgGroup, ArgMatches, SubCommand};
+use commands;
+use error::{GhError, GhResult};
+use git_hub::pulls::{PullRequestUser, ReviewRequest};
 
 pub fn SUBCOMMAND<'a, 'b>() -> App<'a, 'b> {
     SubCommand::with_name("pullreq")
@@ -11,87 +11,1338 @@ pub fn SUBCOMMAND<'a, 'b>() -> App<'a, 'b> {
                 .subcommand(SubCommand::with_name("list")
                                         .about("Lists GitHub Pull Requests.")
                                         .arg(Arg::with_name("owner")
-                                                  .required(true)
-                                                  .help("The GitHub owner of the repository."))
-                                        .arg(Arg::with_name("repo")
-                                                  .required(true)
+                                                  .requires("repository")
+                                                  .help("The GitHub owner of the repository. Defaults to --repo, then the current git checkout."))
+                                        .arg(Arg::with_name("repository")
+                                                  .value_name("repo")
                                                   .help("The GitHub repository."))
-                                        .arg(Arg::with_name("format")
-                                             .short("f")
-                                             .long("format")
-                                             .help("Sets the output format.")
-                                             .value_name("json")
-                                             .takes_value(true)))
+                                        .arg(Arg::with_name("state")
+                                             .short("s")
+                                             .long("state")
+                                             .help("Filters Pull Requests by state.")
+                                             .possible_values(&["open", "closed", "all"])
+                                             .takes_value(true))
+                                        .arg(commands::format_arg())
+                                        .args(&commands::pagination_args()))
                 .subcommand(SubCommand::with_name("create")
-                                        .about("Create a new GitHub Pull Request.")
+                                        .about("Create a new GitHub Pull Request. Takes OWNER REPO TITLE [HEAD [BASE]], or only TITLE to create it on --repo, then the repository of the current git checkout.")
                                         .arg(Arg::with_name("owner")
-                                                  .required(true)
-                                                  .help("The GitHub owner of the repository."))
-                                        .arg(Arg::with_name("repo")
-                                                  .required(true)
+                                                  .help("The GitHub owner of the repository, or the title when it is the only argument."))
+                                        .arg(Arg::with_name("repository")
+                                                  .value_name("repo")
                                                   .help("The GitHub repository."))
                                         .arg(Arg::with_name("title")
-                                                  .required(true)
                                                   .help("The title of the pull request."))
                                         .arg(Arg::with_name("head")
-                                                  .required(true)
-                                                  .help("The name of the branch where your changes are implemented. For cross-repository pull requests in the same network, namespace head with a user like this: username:branch."))
+                                                  .help("The name of the branch where your changes are implemented. For cross-repository pull requests in the same network, namespace head with a user like this: username:branch. Defaults to the branch checked out in the current git checkout."))
                                         .arg(Arg::with_name("base")
-                                                  .required(true)
-                                                  .help("The name of the branch you want the changes pulled into. This should be an existing branch on the current repository. You cannot submit a pull request to one repository that requests a merge to a base of another repository.")))
-
+                                                  .help("The name of the branch you want the changes pulled into. This should be an existing branch on the current repository. You cannot submit a pull request to one repository that requests a merge to a base of another repository. Defaults to the repository's default branch."))
+                                        .arg(Arg::with_name("head-branch")
+                                             .short("H")
+                                             .long("head")
+                                             .help("The head branch, when it isn't given as HEAD.")
+                                             .value_name("branch")
+                                             .takes_value(true)
+                                             .conflicts_with("head"))
+                                        .arg(Arg::with_name("base-branch")
+                                             .short("B")
+                                             .long("base")
+                                             .help("The base branch, when it isn't given as BASE.")
+                                             .value_name("branch")
+                                             .takes_value(true)
+                                             .conflicts_with("base"))
+                                        .arg(Arg::with_name("body")
+                                             .short("b")
+                                             .long("body")
+                                             .help("The contents of the pull request.")
+                                             .value_name("text")
+                                             .takes_value(true)
+                                             .conflicts_with_all(&["body-file", "editor"]))
+                                        .arg(Arg::with_name("body-file")
+                                             .short("F")
+                                             .long("body-file")
+                                             .help("Reads the contents of the pull request from a file. Use - to read from stdin.")
+                                             .value_name("path")
+                                             .takes_value(true)
+                                             .conflicts_with("editor"))
+                                        .arg(Arg::with_name("editor")
+                                             .short("e")
+                                             .long("editor")
+                                             .help("Writes the contents of the pull request in $EDITOR."))
+                                        .arg(Arg::with_name("draft")
+                                             .short("d")
+                                             .long("draft")
+                                             .help("Opens the pull request as a draft."))
+                                        .arg(Arg::with_name("maintainer-can-modify")
+                                             .long("maintainer-can-modify")
+                                             .help("Whether maintainers of the base repository can push to the head branch.")
+                                             .possible_values(&["true", "false"])
+                                             .takes_value(true))
+                                        .arg(commands::format_arg()))
+                .subcommand(SubCommand::with_name("view")
+                                        .about("Shows a single Pull Request with its reviewers, mergeability and checks.")
+                                        .arg(number_arg())
+                                        .arg(commands::format_arg()))
+                .subcommand(SubCommand::with_name("diff")
+                                        .about("Prints the diff of a Pull Request.")
+                                        .arg(number_arg()))
+                .subcommand(SubCommand::with_name("edit")
+                                        .about("Edits a Pull Request. Every change given is made in one go.")
+                                        .arg(number_arg())
+                                        .arg(Arg::with_name("title")
+                                             .short("t")
+                                             .long("title")
+                                             .help("The new title.")
+                                             .value_name("text")
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("body")
+                                             .short("b")
+                                             .long("body")
+                                             .help("The new contents of the pull request.")
+                                             .value_name("text")
+                                             .takes_value(true)
+                                             .conflicts_with("body-file"))
+                                        .arg(Arg::with_name("body-file")
+                                             .short("F")
+                                             .long("body-file")
+                                             .help("Reads the new contents of the pull request from a file. Use - to read from stdin.")
+                                             .value_name("path")
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("base")
+                                             .short("B")
+                                             .long("base")
+                                             .help("The new base branch.")
+                                             .value_name("branch")
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("state")
+                                             .short("s")
+                                             .long("state")
+                                             .help("Closes or reopens the pull request.")
+                                             .possible_values(&["open", "closed"])
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("draft")
+                                             .long("draft")
+                                             .help("Converts the pull request to a draft.")
+                                             .conflicts_with("ready"))
+                                        .arg(Arg::with_name("ready")
+                                             .long("ready")
+                                             .help("Marks a draft pull request ready for review."))
+                                        .arg(list_arg("add-label", "Adds this label.", "name"))
+                                        .arg(list_arg("remove-label", "Removes this label.", "name"))
+                                        .arg(list_arg("add-assignee", "Assigns this user.", "login"))
+                                        .arg(list_arg("remove-assignee", "Unassigns this user.", "login"))
+                                        .arg(list_arg("add-reviewer", "Requests a review from this user, or ORG/TEAM.", "login"))
+                                        .arg(list_arg("remove-reviewer", "Withdraws the review request of this user, or ORG/TEAM.", "login"))
+                                        .arg(commands::format_arg()))
+                .subcommand(SubCommand::with_name("review")
+                                        .about("Approves, requests changes to, or comments on a Pull Request.")
+                                        .arg(number_arg())
+                                        .arg(Arg::with_name("approve")
+                                             .short("a")
+                                             .long("approve")
+                                             .help("Approves the pull request."))
+                                        .arg(Arg::with_name("request-changes")
+                                             .short("r")
+                                             .long("request-changes")
+                                             .help("Requests changes, explained in the body."))
+                                        .arg(Arg::with_name("comment")
+                                             .short("c")
+                                             .long("comment")
+                                             .help("Comments without approving or requesting changes."))
+                                        .group(ArgGroup::with_name("event")
+                                               .args(&["approve", "request-changes", "comment"])
+                                               .required(true))
+                                        .arg(Arg::with_name("body")
+                                             .short("b")
+                                             .long("body")
+                                             .help("The text of the review. Required unless approving.")
+                                             .value_name("text")
+                                             .takes_value(true)
+                                             .conflicts_with_all(&["body-file", "editor"]))
+                                        .arg(Arg::with_name("body-file")
+                                             .short("F")
+                                             .long("body-file")
+                                             .help("Reads the text of the review from a file. Use - to read from stdin.")
+                                             .value_name("path")
+                                             .takes_value(true)
+                                             .conflicts_with("editor"))
+                                        .arg(Arg::with_name("editor")
+                                             .short("e")
+                                             .long("editor")
+                                             .help("Writes the text of the review in $EDITOR."))
+                                        .arg(commands::format_arg()))
+                .subcommand(SubCommand::with_name("reviews")
+                                        .about("Lists the reviews of a Pull Request.")
+                                        .arg(number_arg())
+                                        .arg(commands::format_arg())
+                                        .args(&commands::pagination_args()))
+                .subcommand(SubCommand::with_name("review-comments")
+                                        .about("Lists the comments on lines of a Pull Request's diff, grouped by file.")
+                                        .arg(number_arg())
+                                        .arg(commands::format_arg())
+                                        .args(&commands::pagination_args()))
+                .subcommand(SubCommand::with_name("reviewers")
+                                        .about("Lists, requests or withdraws the review requests of a Pull Request.")
+                                        .arg(number_arg())
+                                        .arg(list_arg("add", "Requests a review from this user, or ORG/TEAM.", "login"))
+                                        .arg(list_arg("remove", "Withdraws the review request of this user, or ORG/TEAM.", "login"))
+                                        .arg(commands::format_arg()))
+                .subcommand(SubCommand::with_name("merge")
+                                        .about("Merges a Pull Request.")
+                                        .arg(number_arg())
+                                        .arg(Arg::with_name("method")
+                                             .short("m")
+                                             .long("method")
+                                             .help("How to merge. Defaults to a merge commit.")
+                                             .possible_values(&["merge", "squash", "rebase"])
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("title")
+                                             .short("t")
+                                             .long("title")
+                                             .help("The title of the merge or squash commit.")
+                                             .value_name("text")
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("message")
+                                             .long("message")
+                                             .help("The message of the merge or squash commit.")
+                                             .value_name("text")
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("sha")
+                                             .long("sha")
+                                             .help("Only merges if the head branch is still at this commit.")
+                                             .value_name("sha")
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("delete-branch")
+                                             .short("d")
+                                             .long("delete-branch")
+                                             .help("Deletes the head branch once merged."))
+                                        .arg(commands::format_arg()))
+                .subcommand(SubCommand::with_name("checkout")
+                                        .about("Fetches a Pull Request into a local branch and checks it out.")
+                                        .arg(number_arg())
+                                        .arg(Arg::with_name("branch")
+                                             .long("branch")
+                                             .help("The local branch to check out. Defaults to pr-NUMBER.")
+                                             .value_name("name")
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("force")
+                                             .long("force")
+                                             .help("Resets the branch, when it is checked out, to a force-pushed Pull Request, discarding its local commits.")))
 }
 
-pub fn handle(matches: &ArgMatches) -> () {
+pub fn handle(matches: &ArgMatches) -> GhResult<()> {
     match matches.subcommand() {
-        ("list", Some(list_matches)) => list_pull_reqs(list_matches) ,
-        ("create", Some(create_matches)) => create_pull_request(create_matches),
-        ("", None)                   => println!("No subcommand was used for orgs"),
+        ("list", Some(list_matches)) => list::handle(list_matches),
+        ("create", Some(create_matches)) => create::handle(create_matches),
+        ("view", Some(view_matches)) => view::handle(view_matches),
+        ("diff", Some(diff_matches)) => diff::handle(diff_matches),
+        ("edit", Some(edit_matches)) => edit::handle(edit_matches),
+        ("review", Some(review_matches)) => review::handle(review_matches),
+        ("reviews", Some(reviews_matches)) => reviews::handle(reviews_matches),
+        ("review-comments", Some(comments_matches)) => review_comments::handle(comments_matches),
+        ("reviewers", Some(reviewers_matches)) => reviewers::handle(reviewers_matches),
+        ("merge", Some(merge_matches)) => merge::handle(merge_matches),
+        ("checkout", Some(checkout_matches)) => checkout::handle(checkout_matches),
+        ("", None)                   => {
+            println!("No subcommand was used for pullreq");
+            Ok(())
+        },
         (_, _)                       => unreachable!()
     }
 }
 
-fn list_pull_reqs(matches: &ArgMatches) -> () {
-   //let owner = matches.value_of("owner").unwrap().to_string();
-   //let repo = matches.value_of("repo").unwrap().to_string();
-   //let url = "https://api.github.com/repos/".to_string() +
-   //     &owner +
-   //     "/" +
-   //     &repo +
-   //     "/pulls";
-   //let config = config::load_config();
-   //let response = GitHub::get(&config.access_token, &url);
-   // let st = GitHub::parse_json(&response);
-   // println!("{}", st);
-}
-
-#[derive(Serialize, Deserialize)]
-struct NewPullRequest {
-    pub title: String,
-    pub head: String,
-    pub base: String
-}
-
-fn create_pull_request(matches: &ArgMatches) -> () {
-   //let owner = matches.value_of("owner").unwrap().to_string();
-   //let repo = matches.value_of("repo").unwrap().to_string();
-   //let url = "https://api.github.com/repos/".to_string() +
-   //     &owner +
-   //     "/" +
-   //     &repo +
-   //     "/pulls";
-   //let pull_req = NewPullRequest {
-   //     title: matches.value_of("title").unwrap().to_string(),
-   //     head: matches.value_of("head").unwrap().to_string(),
-   //     base: matches.value_of("base").unwrap().to_string()
-   // };
-   //let json_str = match serde_json::to_string(&pull_req) {
-   //    Ok(x) => x,
-   //    Err(_) => panic!("Error seriliazing pull request")
-   // };
-   //let bytes = json_str.as_bytes();
-   //let config = config::load_config();
-   //let response = GitHub::post(&config.access_token, &url, bytes);
-   // let st = GitHub::parse_json(&response);
-   // println!("{}", st);
+fn number_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("number")
+        .required(true)
+        .help("The number of the Pull Request.")
+        .validator(commands::is_positive_number)
+}
+
+// A repeatable, comma separated option, such as --add-label
+fn list_arg<'a, 'b>(name: &'a str, help: &'a str, value_name: &'a str) -> Arg<'a, 'b> {
+    Arg::with_name(name)
+        .long(name)
+        .help(help)
+        .value_name(value_name)
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .use_delimiter(true)
+}
+
+// Splits reviewers into users, by login, and teams, given as ORG/TEAM. Only
+// the team's slug is sent, the organization is always the repository's.
+fn review_request(reviewers: Vec<String>) -> ReviewRequest {
+    let mut review_request = ReviewRequest::default();
+    for reviewer in reviewers {
+        match reviewer.find('/') {
+            Some(slash) => review_request.team_reviewers.push(reviewer[slash + 1..].to_owned()),
+            None        => review_request.reviewers.push(reviewer)
+        }
+    }
+    review_request
+}
+
+// Rejects blank names, and names both added and removed, before
+// anything is changed
+fn validate(what: &str, add: &[String], remove: &[String]) -> GhResult<()> {
+    if add.iter().chain(remove.iter()).any(|x| x.trim().is_empty()) {
+        return Err(GhError::Usage(format!("A {} can't be blank.", what)));
+    }
+    match add.iter().find(|x| remove.contains(x)) {
+        Some(name) => Err(GhError::Usage(format!("The {} {} is both added and removed.", what, name))),
+        None       => Ok(())
+    }
+}
+
+// Team reviewers are requested by slug alone, so they must be teams of
+// the repository's owner
+fn validate_teams<'a, I>(owner: &str, reviewers: I) -> GhResult<()> where I: Iterator<Item = &'a String> {
+    for reviewer in reviewers {
+        if let Some(slash) = reviewer.find('/') {
+            let (org, team) = (&reviewer[..slash], &reviewer[slash + 1..]);
+            if !org.eq_ignore_ascii_case(owner) || team.is_empty() {
+                return Err(GhError::Usage(format!("{} is not a team of {}, expected {}/TEAM.", reviewer, owner, owner)));
+            }
+        }
+    }
+    Ok(())
+}
+
+// The login of an author or reviewer, or ghost once their account has been
+// deleted
+fn login(user: &Option<PullRequestUser>) -> &str {
+    user.as_ref().map(|x| x.login.as_str()).unwrap_or("ghost")
+}
+
+fn explain_error(error: GhError) -> GhError {
+    commands::explain_error(error, FORBIDDEN, NOT_FOUND)
+}
+
+const FORBIDDEN: &'static str = "403 Forbidden. Does your OAuth token have sufficient scope? A minimum of `repo` is required for private repositories. See https://developer.github.com/v3/pulls/";
+const NOT_FOUND: &'static str = "404 Not Found. Does the repository or Pull Request exist, and can your credentials see it? See https://developer.github.com/v3/pulls/";
+
+#[cfg(test)]
+mod tests {
+use super::{review_request, validate, validate_teams};
+
+    fn names(xs: &[&str]) -> Vec<String> {
+        xs.iter().map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn test_validate() -> () {
+        assert!(validate("label", &names(&["bug"]), &names(&["wontfix"])).is_ok());
+        assert!(validate("label", &names(&["bug", " "]), &[]).is_err());
+        assert!(validate("label", &names(&["bug"]), &names(&["bug"])).is_err());
+    }
+
+    #[test]
+    fn test_validate_teams() -> () {
+        let reviewers = names(&["hubot", "Octocat/justice-league"]);
+        assert!(validate_teams("octocat", reviewers.iter()).is_ok());
+        assert!(validate_teams("octocat", names(&["github/justice-league"]).iter()).is_err());
+        assert!(validate_teams("octocat", names(&["octocat/"]).iter()).is_err());
+    }
+
+    #[test]
+    fn test_review_request() -> () {
+        let review_request = review_request(vec!["octocat".to_owned(), "github/justice-league".to_owned()]);
+        assert_eq!(review_request.reviewers, vec!["octocat".to_owned()]);
+        assert_eq!(review_request.team_reviewers, vec!["justice-league".to_owned()]);
+    }
+}
+
+mod list {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, NL, pagination_from_args, repo_from_args};
+use error::GhResult;
+use evidence::json_ops;
+use git_hub::pulls;
+use git_hub::pulls::PullRequestSummary;
+use super::{explain_error, login};
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let client = client_from_args(matches)?;
+        let (owner, repo) = repo_from_args(matches, &client)?;
+        let state = matches.value_of("state");
+        let pagination = pagination_from_args(matches);
+        let pulls = pulls::get_pull_requests(&owner, &repo, state, &client, &pagination)
+                          .map_err(explain_error)?;
+        let output = &format_output(pulls, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    fn format_output(pulls: Vec<PullRequestSummary>, is_json: bool) -> GhResult<String> {
+        if is_json {
+            json_ops::to_pretty_json(&pulls, SERIALIZE_PULL_REQUEST_SUMMARY)
+        } else {
+            let mut output = String::with_capacity(100);
+            let header = format!("{0: <8} {1: <8} {2: <15} {3: <30} {4: <50}", "number", "state", "author", "head", "title");
+            output.push_str(&header);
+            output.push_str(NL);
+            for pull in pulls {
+                let line = format!("{0: <8} {1: <8} {2: <15} {3: <30} {4: <50}",
+                                   pull.number, pull.state, login(&pull.user), pull.head.label, pull.title);
+                output.push_str(&line);
+                output.push_str(NL);
+            }
+            Ok(output)
+        }
+    }
+
+    const SERIALIZE_PULL_REQUEST_SUMMARY: &'static str = "Error serializing GitHub Pull Request Summary JSON.";
+
+    #[cfg(test)]
+    mod tests {
+    use evidence::json_ops;
+    use super::format_output;
+
+        #[test]
+        fn test_format_output_table() -> () {
+            let body = "[{\"id\":1,\"number\":1347,\"state\":\"open\",\"title\":\"new-feature\",\
+                        \"url\":\"https://api.github.com/repos/octocat/Hello-World/pulls/1347\",\
+                        \"html_url\":\"https://github.com/octocat/Hello-World/pull/1347\",\
+                        \"user\":{\"login\":\"octocat\",\"id\":1},\
+                        \"head\":{\"label\":\"octocat:new-topic\",\"ref\":\"new-topic\",\"sha\":\"6dcb09b\"},\
+                        \"base\":{\"label\":\"octocat:master\",\"ref\":\"master\",\"sha\":\"6dcb09b\"},\
+                        \"body\":null,\"created_at\":\"2011-01-26T19:01:12Z\",\"updated_at\":\"2011-01-26T19:01:12Z\"}]";
+            let output = format_output(json_ops::from_str(body, "").unwrap(), false).unwrap();
+            let lines: Vec<&str> = output.trim().lines().collect();
+            assert_eq!(lines.len(), 2);
+            assert!(lines[0].starts_with("number"));
+            assert!(lines[1].starts_with("1347"));
+            assert!(lines[1].contains("octocat:new-topic"));
+            assert!(lines[1].contains("new-feature"));
+        }
+    }
+}
+
+mod create {
+use clap::ArgMatches;
+use commands::{body_from_args, client_from_args, is_json, repo_from_args};
+use error::{GhError, GhResult};
+use evidence::json_ops;
+use git;
+use git_hub::{pulls, repos};
+use git_hub::pulls::{NewPullRequest, PullRequestSummary};
+use super::explain_error;
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        // OWNER REPO TITLE [HEAD [BASE]], or TITLE alone
+        let title = match (matches.value_of("owner"), matches.value_of("repository"), matches.value_of("title")) {
+            (_, _, Some(title))       => title,
+            (Some(title), None, None) => title,
+            _                         => return Err(GhError::Usage(NO_TITLE.to_owned()))
+        };
+        let client = client_from_args(matches)?;
+        let (owner, repo) = repo_from_args(matches, &client)?;
+        let head = match matches.value_of("head").or(matches.value_of("head-branch")) {
+            Some(head) => head.to_owned(),
+            None       => {
+                let branch = git::current_branch().ok_or(GhError::Usage(NO_HEAD.to_owned()))?;
+                let host = git::web_host(client.config().api_url());
+                qualify_head(branch, &owner, &repo, git::remote_repo("origin", &host))
+            }
+        };
+        let base = match matches.value_of("base").or(matches.value_of("base-branch")) {
+            Some(base) => base.to_owned(),
+            None       => repos::get_repo(&owner, &repo, &client).map_err(explain_error)?.default_branch
+        };
+        let pull_request = NewPullRequest {
+            title: title.to_owned(),
+            head: head,
+            base: base,
+            body: body_from_args(matches, "pull request", "PULLREQ_EDITMSG", EDITOR_TEMPLATE)?,
+            draft: if matches.is_present("draft") { Some(true) } else { None },
+            maintainer_can_modify: matches.value_of("maintainer-can-modify").map(|x| x == "true")
+        };
+        let pull = pulls::create_pull_request(&owner, &repo, &pull_request, &client)
+                         .map_err(explain_error)?;
+        let output = &format_output(pull, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    // A branch checked out from a fork, whose origin isn't the repository
+    // the Pull Request is opened on, is namespaced with the fork's owner,
+    // as GitHub would otherwise look for it in that repository.
+    fn qualify_head(branch: String, owner: &str, repo: &str, origin: Option<(String, String)>) -> String {
+        match origin {
+            Some((ref o, ref r)) if !(o.eq_ignore_ascii_case(owner) && r.eq_ignore_ascii_case(repo)) => {
+                format!("{}:{}", o, branch)
+            },
+            _ => branch
+        }
+    }
+
+    fn format_output(pull: PullRequestSummary, is_json: bool) -> GhResult<String> {
+        if is_json {
+            json_ops::to_pretty_json(&pull, SERIALIZE_PULL_REQUEST_SUMMARY)
+        } else {
+            Ok(format!("{0: <8} {1: <50}", pull.number, pull.html_url))
+        }
+    }
+
+    const NO_TITLE: &'static str = "No title given. Pass TITLE, or OWNER REPO TITLE [HEAD [BASE]].";
+    const NO_HEAD: &'static str = "No head branch given. Pass HEAD or --head, or run gh inside a git checkout with a branch checked out.";
+
+    const EDITOR_TEMPLATE: &'static str = "\n# Describe your pull request above. This line is ignored.\n";
+
+    const SERIALIZE_PULL_REQUEST_SUMMARY: &'static str = "Error serializing GitHub Pull Request Summary JSON.";
+
+    #[cfg(test)]
+    mod tests {
+    use error::{ApiError, GhError};
+    use evidence::json_ops;
+    use hyper::status::StatusCode;
+    use super::{explain_error, format_output, qualify_head};
+
+        fn repo(owner: &str, name: &str) -> Option<(String, String)> {
+            Some((owner.to_owned(), name.to_owned()))
+        }
+
+        #[test]
+        fn test_qualify_head_same_repository() -> () {
+            assert_eq!(qualify_head("topic".to_owned(), "octocat", "Hello-World", repo("octocat", "hello-world")),
+                       "topic");
+            assert_eq!(qualify_head("topic".to_owned(), "octocat", "Hello-World", None), "topic");
+        }
+
+        #[test]
+        fn test_qualify_head_fork() -> () {
+            assert_eq!(qualify_head("topic".to_owned(), "octocat", "Hello-World", repo("hubot", "Hello-World")),
+                       "hubot:topic");
+        }
+
+        #[test]
+        fn test_explain_error_unprocessable_entity() -> () {
+            let body = "{\"message\":\"Validation Failed\",\
+                        \"errors\":[{\"resource\":\"PullRequest\",\"code\":\"custom\",\
+                        \"message\":\"No commits between master and new-topic\"}]}";
+            let api_error: ApiError = json_ops::from_str(body, "").unwrap();
+            let error = explain_error(GhError::Api(StatusCode::UnprocessableEntity, api_error));
+            assert_eq!(error.to_string(),
+                       "422 Unprocessable Entity: Validation Failed (No commits between master and new-topic)");
+            assert_eq!(error.exit_code(), 5);
+        }
+
+        #[test]
+        fn test_format_output_created() -> () {
+            let body = "{\"id\":1,\"number\":1347,\"state\":\"open\",\"title\":\"new-feature\",\
+                        \"url\":\"https://api.github.com/repos/octocat/Hello-World/pulls/1347\",\
+                        \"html_url\":\"https://github.com/octocat/Hello-World/pull/1347\",\
+                        \"user\":{\"login\":\"octocat\",\"id\":1},\
+                        \"head\":{\"label\":\"octocat:new-topic\",\"ref\":\"new-topic\",\"sha\":\"6dcb09b\"},\
+                        \"base\":{\"label\":\"octocat:master\",\"ref\":\"master\",\"sha\":\"6dcb09b\"},\
+                        \"body\":null,\"draft\":true,\"created_at\":\"2011-01-26T19:01:12Z\",\"updated_at\":\"2011-01-26T19:01:12Z\"}";
+            assert_eq!(format_output(json_ops::from_str(body, "").unwrap(), false).unwrap().trim(),
+                       "1347     https://github.com/octocat/Hello-World/pull/1347");
+        }
+    }
+}
+
+mod view {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, NL, repo_from_args};
+use error::GhResult;
+use evidence::json_ops;
+use git_hub::{checks, pulls};
+use git_hub::checks::CheckRuns;
+use git_hub::pulls::PullRequest;
+use super::{explain_error, login};
+
+    // A Pull Request together with the checks of its head commit, for
+    // --format json
+    #[derive(Serialize)]
+    struct PullRequestWithChecks {
+        pull_request: PullRequest,
+        checks: Option<CheckRuns>
+    }
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let client = client_from_args(matches)?;
+        let (owner, repo) = repo_from_args(matches, &client)?;
+        let number = matches.value_of("number").unwrap().parse().unwrap();
+        let pull   = pulls::get_pull_request(&owner, &repo, number, &client).map_err(explain_error)?;
+        // Checks are only a summary here, a token without access to them
+        // shouldn't keep the Pull Request itself from being shown
+        let checks = checks::list_check_runs(&owner, &repo, &pull.head.sha, &client).ok();
+        let output = &format_output(pull, checks, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    fn format_output(pull: PullRequest, checks: Option<CheckRuns>, is_json: bool) -> GhResult<String> {
+        if is_json {
+            return json_ops::to_pretty_json(&PullRequestWithChecks { pull_request: pull, checks: checks },
+                                            SERIALIZE_PULL_REQUEST);
+        }
+        let state = if pull.merged {
+            "merged".to_owned()
+        } else if pull.draft == Some(true) {
+            format!("{} (draft)", pull.state)
+        } else {
+            pull.state.clone()
+        };
+        let mut reviewers: Vec<String> = pull.requested_reviewers.iter().map(|x| x.login.clone()).collect();
+        reviewers.extend(pull.requested_teams.iter().map(|x| format!("team:{}", x.slug)));
+        let mergeable = match (pull.mergeable, pull.mergeable_state.as_ref()) {
+            (_, Some(state)) if state != "unknown" => state.clone(),
+            (Some(true), _)                        => "clean".to_owned(),
+            (Some(false), _)                       => "conflicting".to_owned(),
+            (None, _)                              => "unknown".to_owned()
+        };
+        let branches = format!("{} -> {}", pull.head.label, pull.base.label);
+        let changes = format!("{} commits, {} files, +{} -{}",
+                              pull.commits, pull.changed_files, pull.additions, pull.deletions);
+        let mut output = String::with_capacity(500);
+        output.push_str(&format!("#{} {}", pull.number, pull.title));
+        output.push_str(NL);
+        for &(name, value) in &[("state", state.as_str()),
+                                ("author", login(&pull.user)),
+                                ("branches", &branches),
+                                ("changes", &changes),
+                                ("reviewers", &reviewers.join(", ")),
+                                ("mergeable", &mergeable),
+                                ("checks", &checks_summary(checks.as_ref())),
+                                ("url", pull.html_url.as_str())] {
+            output.push_str(&format!("{0: <10} {1}", name, value));
+            output.push_str(NL);
+        }
+        if let Some(ref body) = pull.body {
+            output.push_str(NL);
+            output.push_str(body.trim());
+            output.push_str(NL);
+        }
+        Ok(output)
+    }
+
+    // e.g. "3 passed, 1 failed, 1 pending"
+    fn checks_summary(checks: Option<&CheckRuns>) -> String {
+        let runs = match checks {
+            Some(checks) if !checks.check_runs.is_empty() => &checks.check_runs,
+            Some(_) => return "none".to_owned(),
+            None    => return "unknown".to_owned()
+        };
+        let (mut passed, mut failed, mut pending) = (0, 0, 0);
+        for run in runs {
+            match run.conclusion.as_ref().map(|x| x.as_str()) {
+                None                                                 => pending += 1,
+                Some("success") | Some("neutral") | Some("skipped") => passed += 1,
+                Some(_)                                              => failed += 1
+            }
+        }
+        format!("{} passed, {} failed, {} pending", passed, failed, pending)
+    }
+
+    const SERIALIZE_PULL_REQUEST: &'static str = "Error serializing GitHub Pull Request JSON.";
+
+    #[cfg(test)]
+    mod tests {
+    use evidence::json_ops;
+    use super::{checks_summary, format_output};
+
+        const PULL_REQUEST: &'static str = "{\"id\":1,\"number\":1347,\"state\":\"open\",\"title\":\"Amazing new feature\",\
+                                            \"url\":\"https://api.github.com/repos/octocat/Hello-World/pulls/1347\",\
+                                            \"html_url\":\"https://github.com/octocat/Hello-World/pull/1347\",\
+                                            \"user\":{\"login\":\"octocat\",\"id\":1},\
+                                            \"head\":{\"label\":\"octocat:new-topic\",\"ref\":\"new-topic\",\"sha\":\"6dcb09b\"},\
+                                            \"base\":{\"label\":\"octocat:master\",\"ref\":\"master\",\"sha\":\"6dcb09b\"},\
+                                            \"body\":\"Please pull these awesome changes in!\",\"draft\":false,\
+                                            \"merged\":false,\"mergeable\":true,\"mergeable_state\":\"clean\",\"merged_by\":null,\
+                                            \"requested_reviewers\":[{\"login\":\"other_user\",\"id\":2}],\
+                                            \"requested_teams\":[{\"name\":\"Justice League\",\"slug\":\"justice-league\"}],\
+                                            \"comments\":10,\"review_comments\":0,\"commits\":3,\"additions\":100,\
+                                            \"deletions\":3,\"changed_files\":5,\
+                                            \"created_at\":\"2011-01-26T19:01:12Z\",\"updated_at\":\"2011-01-26T19:01:12Z\",\
+                                            \"closed_at\":null,\"merged_at\":null}";
+
+        const CHECK_RUNS: &'static str = "{\"total_count\":3,\"check_runs\":[\
+                                          {\"id\":1,\"name\":\"build\",\"status\":\"completed\",\"conclusion\":\"success\",\"html_url\":null},\
+                                          {\"id\":2,\"name\":\"lint\",\"status\":\"completed\",\"conclusion\":\"failure\",\"html_url\":null},\
+                                          {\"id\":3,\"name\":\"test\",\"status\":\"in_progress\",\"conclusion\":null,\"html_url\":null}]}";
+
+        #[test]
+        fn test_format_output() -> () {
+            let output = format_output(json_ops::from_str(PULL_REQUEST, "").unwrap(),
+                                       Some(json_ops::from_str(CHECK_RUNS, "").unwrap()),
+                                       false).unwrap();
+            let lines: Vec<&str> = output.trim().lines().collect();
+            assert_eq!(lines[0], "#1347 Amazing new feature");
+            assert_eq!(lines[1], "state      open");
+            assert_eq!(lines[3], "branches   octocat:new-topic -> octocat:master");
+            assert_eq!(lines[4], "changes    3 commits, 5 files, +100 -3");
+            assert_eq!(lines[5], "reviewers  other_user, team:justice-league");
+            assert_eq!(lines[6], "mergeable  clean");
+            assert_eq!(lines[7], "checks     1 passed, 1 failed, 1 pending");
+            assert_eq!(lines[10], "Please pull these awesome changes in!");
+        }
+
+        #[test]
+        fn test_checks_summary_unavailable() -> () {
+            assert_eq!(checks_summary(None), "unknown");
+        }
+    }
+}
+
+mod diff {
+use clap::ArgMatches;
+use commands::{client_from_args, repo_from_args};
+use error::GhResult;
+use git_hub::pulls;
+use super::explain_error;
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let client = client_from_args(matches)?;
+        let (owner, repo) = repo_from_args(matches, &client)?;
+        let number = matches.value_of("number").unwrap().parse().unwrap();
+        let diff = pulls::get_pull_request_diff(&owner, &repo, number, &client)
+                         .map_err(explain_error)?;
+        print!("{}", diff);
+        Ok(())
+    }
+}
+
+mod edit {
+use clap::ArgMatches;
+use commands::{body_from_args, client_from_args, is_json, repo_from_args, values};
+use error::{GhError, GhResult};
+use evidence::json_ops;
+use git_hub::{issues, pulls};
+use git_hub::pulls::{EditPullRequest, PullRequest};
+use super::{explain_error, review_request, validate, validate_teams};
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let client = client_from_args(matches)?;
+        let (owner, repo) = repo_from_args(matches, &client)?;
+        let number = matches.value_of("number").unwrap().parse().unwrap();
+        let edit = EditPullRequest {
+            title: matches.value_of("title").map(|x| x.to_owned()),
+            body: body_from_args(matches, "pull request", "PULLREQ_EDITMSG", "")?,
+            base: matches.value_of("base").map(|x| x.to_owned()),
+            state: matches.value_of("state").map(|x| x.to_owned())
+        };
+        let draft = if matches.is_present("draft") {
+            Some(true)
+        } else if matches.is_present("ready") {
+            Some(false)
+        } else {
+            None
+        };
+        let (add_labels, remove_labels) = (values(matches, "add-label"), values(matches, "remove-label"));
+        let (add_assignees, remove_assignees) = (values(matches, "add-assignee"), values(matches, "remove-assignee"));
+        let (add_reviewers, remove_reviewers) = (values(matches, "add-reviewer"), values(matches, "remove-reviewer"));
+        validate("label", &add_labels, &remove_labels)?;
+        validate("assignee", &add_assignees, &remove_assignees)?;
+        validate("reviewer", &add_reviewers, &remove_reviewers)?;
+        validate_teams(&owner, add_reviewers.iter().chain(remove_reviewers.iter()))?;
+        let (add_reviewers, remove_reviewers) = (review_request(add_reviewers), review_request(remove_reviewers));
+        let patch = edit.title.is_some() || edit.body.is_some() || edit.base.is_some() || edit.state.is_some();
+        let others = draft.is_some() || !add_labels.is_empty() || !remove_labels.is_empty() ||
+                     !add_assignees.is_empty() || !remove_assignees.is_empty() ||
+                     !add_reviewers.reviewers.is_empty() || !add_reviewers.team_reviewers.is_empty() ||
+                     !remove_reviewers.reviewers.is_empty() || !remove_reviewers.team_reviewers.is_empty();
+        if !patch && !others {
+            return Err(GhError::Usage(NOTHING_TO_EDIT.to_owned()));
+        }
+        // The draft mutation needs the Pull Request's node id, looked up
+        // before anything is changed
+        let node_id = match draft {
+            Some(_) => Some(pulls::get_pull_request(&owner, &repo, number, &client).map_err(explain_error)?.node_id),
+            None    => None
+        };
+
+        let mut done = vec![];
+        let pull = if patch {
+            Some(step(&mut done, "title, body, base or state", || {
+                pulls::edit_pull_request(&owner, &repo, number, &edit, &client)
+            })?)
+        } else {
+            None
+        };
+        if let (Some(draft), Some(node_id)) = (draft, node_id) {
+            step(&mut done, if draft { "converted to draft" } else { "marked ready for review" }, || {
+                pulls::set_draft(&node_id, draft, &client)
+            })?;
+        }
+        // Labels and assignees belong to the Pull Request's Issue
+        if !add_labels.is_empty() {
+            step(&mut done, "labels added", || issues::add_labels(&owner, &repo, number, &add_labels, &client))?;
+        }
+        for label in &remove_labels {
+            step(&mut done, "labels removed", || issues::remove_label(&owner, &repo, number, label, &client))?;
+        }
+        if !add_assignees.is_empty() {
+            step(&mut done, "assignees added", || {
+                issues::add_assignees(&owner, &repo, number, &add_assignees, &client)
+            })?;
+        }
+        if !remove_assignees.is_empty() {
+            step(&mut done, "assignees removed", || {
+                issues::remove_assignees(&owner, &repo, number, &remove_assignees, &client)
+            })?;
+        }
+        if !add_reviewers.reviewers.is_empty() || !add_reviewers.team_reviewers.is_empty() {
+            step(&mut done, "reviewers requested", || {
+                pulls::request_reviewers(&owner, &repo, number, &add_reviewers, &client)
+            })?;
+        }
+        if !remove_reviewers.reviewers.is_empty() || !remove_reviewers.team_reviewers.is_empty() {
+            step(&mut done, "review requests withdrawn", || {
+                pulls::remove_requested_reviewers(&owner, &repo, number, &remove_reviewers, &client)
+            })?;
+        }
+        // Anything changed after the PATCH isn't in its response
+        let pull = match pull {
+            Some(pull) if !others => pull,
+            _                     => pulls::get_pull_request(&owner, &repo, number, &client).map_err(explain_error)?
+        };
+        let output = &format_output(pull, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    // Makes one of the changes of an edit, which are separate requests. When
+    // one fails the others aren't undone, so those already made, in `done`,
+    // are reported before the error.
+    fn step<T, F>(done: &mut Vec<&'static str>, change: &'static str, make: F) -> GhResult<T>
+        where F: FnOnce() -> GhResult<T> {
+        match make() {
+            Ok(x)    => {
+                if !done.contains(&change) {
+                    done.push(change);
+                }
+                Ok(x)
+            },
+            Err(e)   => {
+                if !done.is_empty() {
+                    eprintln!("gh: the Pull Request was partly edited, already changed: {}", done.join(", "));
+                }
+                Err(explain_error(e))
+            }
+        }
+    }
+
+    fn format_output(pull: PullRequest, is_json: bool) -> GhResult<String> {
+        if is_json {
+            json_ops::to_pretty_json(&pull, SERIALIZE_PULL_REQUEST)
+        } else {
+            Ok(format!("{0: <8} {1: <50}", pull.number, pull.html_url))
+        }
+    }
+
+    const NOTHING_TO_EDIT: &'static str = "Nothing to edit. Pass at least one change, see gh pullreq edit --help.";
+
+    const SERIALIZE_PULL_REQUEST: &'static str = "Error serializing GitHub Pull Request JSON.";
+}
+
+mod review {
+use clap::ArgMatches;
+use commands::{body_from_args, client_from_args, is_json, repo_from_args};
+use error::{GhError, GhResult};
+use evidence::json_ops;
+use git_hub::reviews;
+use git_hub::reviews::{NewReview, Review};
+use super::explain_error;
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let client = client_from_args(matches)?;
+        let (owner, repo) = repo_from_args(matches, &client)?;
+        let number = matches.value_of("number").unwrap().parse().unwrap();
+        let event = if matches.is_present("approve") {
+            "APPROVE"
+        } else if matches.is_present("request-changes") {
+            "REQUEST_CHANGES"
+        } else {
+            "COMMENT"
+        };
+        let body = body_from_args(matches, "review", "PULLREQ_REVIEWMSG", EDITOR_TEMPLATE)?;
+        let body = match body {
+            Some(ref body) if body.trim().is_empty() => None,
+            body                                     => body
+        };
+        if body.is_none() && event != "APPROVE" {
+            return Err(GhError::Usage(BODY_REQUIRED.to_owned()));
+        }
+        let review = NewReview {
+            event: event.to_owned(),
+            body: body
+        };
+        let review = reviews::create_review(&owner, &repo, number, &review, &client)
+                             .map_err(explain_error)?;
+        let output = &format_output(review, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    fn format_output(review: Review, is_json: bool) -> GhResult<String> {
+        if is_json {
+            json_ops::to_pretty_json(&review, SERIALIZE_REVIEW)
+        } else {
+            Ok(format!("{0: <18} {1}", review.state, review.html_url))
+        }
+    }
+
+    const EDITOR_TEMPLATE: &'static str = "\n# Write your review above. This line is ignored.\n";
+    const BODY_REQUIRED: &'static str = "A review that requests changes or comments needs a body. Pass --body, --body-file or --editor.";
+
+    const SERIALIZE_REVIEW: &'static str = "Error serializing GitHub Pull Request Review JSON.";
+
+    #[cfg(test)]
+    mod tests {
+    use evidence::json_ops;
+    use super::format_output;
+
+        const REVIEW: &'static str = "{\"id\":80,\"user\":{\"login\":\"octocat\",\"id\":1},\"body\":null,\
+                                      \"state\":\"APPROVED\",\
+                                      \"html_url\":\"https://github.com/octocat/Hello-World/pull/12#pullrequestreview-80\",\
+                                      \"commit_id\":\"ecdd80bb57125d7ba9641ffaa4d7d2c19d3f3091\",\
+                                      \"submitted_at\":\"2019-11-17T17:43:43Z\"}";
+
+        #[test]
+        fn test_format_output() -> () {
+            assert_eq!(format_output(json_ops::from_str(REVIEW, "").unwrap(), false).unwrap(),
+                       "APPROVED           https://github.com/octocat/Hello-World/pull/12#pullrequestreview-80");
+        }
+
+        #[test]
+        fn test_format_output_json() -> () {
+            let output = format_output(json_ops::from_str(REVIEW, "").unwrap(), true).unwrap();
+            assert!(output.contains("\"state\": \"APPROVED\""));
+        }
+    }
+}
+
+mod reviews {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, NL, pagination_from_args, repo_from_args};
+use error::GhResult;
+use evidence::json_ops;
+use git_hub::reviews;
+use git_hub::reviews::Review;
+use super::{explain_error, login};
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let client = client_from_args(matches)?;
+        let (owner, repo) = repo_from_args(matches, &client)?;
+        let number = matches.value_of("number").unwrap().parse().unwrap();
+        let pagination = pagination_from_args(matches);
+        let reviews = reviews::list_reviews(&owner, &repo, number, &client, &pagination)
+                              .map_err(explain_error)?;
+        let output = &format_output(reviews, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    fn format_output(reviews: Vec<Review>, is_json: bool) -> GhResult<String> {
+        if is_json {
+            return json_ops::to_pretty_json(&reviews, SERIALIZE_REVIEWS);
+        }
+        let mut output = String::with_capacity(100);
+        output.push_str(&format!("{0: <12} {1: <18} {2: <15} {3}", "id", "state", "reviewer", "submitted"));
+        output.push_str(NL);
+        for review in reviews {
+            let reviewer = login(&review.user);
+            let line = format!("{0: <12} {1: <18} {2: <15} {3}",
+                               review.id, review.state, reviewer, review.submitted_at.as_ref().map(|x| x.as_str()).unwrap_or(""));
+            output.push_str(&line);
+            output.push_str(NL);
+        }
+        Ok(output)
+    }
+
+    const SERIALIZE_REVIEWS: &'static str = "Error serializing GitHub Pull Request Reviews JSON.";
+
+    #[cfg(test)]
+    mod tests {
+    use evidence::json_ops;
+    use super::format_output;
+
+        #[test]
+        fn test_format_output_table() -> () {
+            let body = "[{\"id\":80,\"user\":{\"login\":\"octocat\",\"id\":1},\"body\":\"Here is the body for the review.\",\
+                        \"state\":\"APPROVED\",\"html_url\":\"https://github.com/octocat/Hello-World/pull/12#pullrequestreview-80\",\
+                        \"commit_id\":\"ecdd80bb57125d7ba9641ffaa4d7d2c19d3f3091\",\"submitted_at\":\"2019-11-17T17:43:43Z\"},\
+                        {\"id\":81,\"user\":null,\"body\":\"\",\"state\":\"PENDING\",\
+                        \"html_url\":\"https://github.com/octocat/Hello-World/pull/12#pullrequestreview-81\"}]";
+            let output = format_output(json_ops::from_str(body, "").unwrap(), false).unwrap();
+            let lines: Vec<&str> = output.trim().lines().collect();
+            assert_eq!(lines.len(), 3);
+            assert_eq!(lines[1], "80           APPROVED           octocat         2019-11-17T17:43:43Z");
+            assert_eq!(lines[2].trim(), "81           PENDING            ghost");
+        }
+    }
+}
+
+mod review_comments {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, NL, pagination_from_args, repo_from_args};
+use error::GhResult;
+use evidence::json_ops;
+use git_hub::reviews;
+use git_hub::reviews::ReviewComment;
+use super::{explain_error, login};
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let client = client_from_args(matches)?;
+        let (owner, repo) = repo_from_args(matches, &client)?;
+        let number = matches.value_of("number").unwrap().parse().unwrap();
+        let pagination = pagination_from_args(matches);
+        let comments = reviews::list_review_comments(&owner, &repo, number, &client, &pagination)
+                               .map_err(explain_error)?;
+        let output = &format_output(comments, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    // Files in the order they were first commented on, each with its comments
+    fn group_by_file(comments: Vec<ReviewComment>) -> Vec<(String, Vec<ReviewComment>)> {
+        let mut files: Vec<(String, Vec<ReviewComment>)> = vec![];
+        for comment in comments {
+            let index = files.iter().position(|file| file.0 == comment.path);
+            match index {
+                Some(index) => files[index].1.push(comment),
+                None        => files.push((comment.path.clone(), vec![comment]))
+            }
+        }
+        files
+    }
+
+    // e.g.
+    //   src/main.rs
+    //       L12    octocat         Great stuff!
+    //       -      hubot           This line is gone now.
+    fn format_output(comments: Vec<ReviewComment>, is_json: bool) -> GhResult<String> {
+        if is_json {
+            return json_ops::to_pretty_json(&comments, SERIALIZE_REVIEW_COMMENTS);
+        }
+        let mut output = String::with_capacity(500);
+        for (path, comments) in group_by_file(comments) {
+            output.push_str(&path);
+            output.push_str(NL);
+            for comment in comments {
+                let line = comment.line.map(|x| format!("L{}", x)).unwrap_or("-".to_owned());
+                let author = login(&comment.user);
+                for (i, text) in comment.body.trim().lines().enumerate() {
+                    if i == 0 {
+                        output.push_str(&format!("    {0: <6} {1: <15} {2}", line, author, text));
+                    } else {
+                        output.push_str(&format!("    {0: <6} {1: <15} {2}", "", "", text));
+                    }
+                    output.push_str(NL);
+                }
+            }
+        }
+        Ok(output)
+    }
+
+    const SERIALIZE_REVIEW_COMMENTS: &'static str = "Error serializing GitHub Pull Request Review Comments JSON.";
+
+    #[cfg(test)]
+    mod tests {
+    use evidence::json_ops;
+    use super::format_output;
+
+        fn comment(id: u32, path: &str, line: &str, body: &str) -> String {
+            format!("{{\"id\":{},\"pull_request_review_id\":42,\"path\":\"{}\",\"line\":{},\"original_line\":12,\
+                     \"diff_hunk\":\"@@ -16,33 +16,40 @@\",\"user\":{{\"login\":\"octocat\",\"id\":1}},\"body\":\"{}\",\
+                     \"in_reply_to_id\":null,\"html_url\":\"https://github.com/octocat/Hello-World/pull/1#discussion-diff-1\",\
+                     \"created_at\":\"2011-04-14T16:00:49Z\",\"updated_at\":\"2011-04-14T16:00:49Z\"}}",
+                    id, path, line, body)
+        }
+
+        #[test]
+        fn test_format_output_grouped_by_file() -> () {
+            let body = format!("[{},{},{}]",
+                               comment(1, "src/main.rs", "12", "Great stuff!"),
+                               comment(2, "README.md", "null", "Typo"),
+                               comment(3, "src/main.rs", "14", "Also this.\\nAnd that."));
+            let output = format_output(json_ops::from_str(&body, "").unwrap(), false).unwrap();
+            let lines: Vec<&str> = output.trim().lines().collect();
+            assert_eq!(lines, vec!["src/main.rs",
+                                   "    L12    octocat         Great stuff!",
+                                   "    L14    octocat         Also this.",
+                                   "                           And that.",
+                                   "README.md",
+                                   "    -      octocat         Typo"]);
+        }
+    }
+}
+
+mod reviewers {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, NL, repo_from_args, values};
+use error::GhResult;
+use evidence::json_ops;
+use git_hub::pulls;
+use git_hub::pulls::PullRequest;
+use super::{explain_error, review_request, validate, validate_teams};
+
+    // Lists who is asked to review, after making any requests or
+    // withdrawals given
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let client = client_from_args(matches)?;
+        let (owner, repo) = repo_from_args(matches, &client)?;
+        let number = matches.value_of("number").unwrap().parse().unwrap();
+        let (add, remove) = (values(matches, "add"), values(matches, "remove"));
+        validate("reviewer", &add, &remove)?;
+        validate_teams(&owner, add.iter().chain(remove.iter()))?;
+        let (add, remove) = (review_request(add), review_request(remove));
+        let mut pull = None;
+        if !add.reviewers.is_empty() || !add.team_reviewers.is_empty() {
+            pull = Some(pulls::request_reviewers(&owner, &repo, number, &add, &client).map_err(explain_error)?);
+        }
+        if !remove.reviewers.is_empty() || !remove.team_reviewers.is_empty() {
+            pull = Some(pulls::remove_requested_reviewers(&owner, &repo, number, &remove, &client).map_err(explain_error)?);
+        }
+        let pull = match pull {
+            Some(pull) => pull,
+            None       => pulls::get_pull_request(&owner, &repo, number, &client).map_err(explain_error)?
+        };
+        let output = &format_output(&owner, pull, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    // One reviewer per line, teams as ORG/TEAM like they're given
+    fn format_output(owner: &str, pull: PullRequest, is_json: bool) -> GhResult<String> {
+        if is_json {
+            return json_ops::to_pretty_json(&ReviewRequests {
+                users: pull.requested_reviewers.into_iter().map(|x| x.login).collect(),
+                teams: pull.requested_teams.into_iter().map(|x| x.slug).collect()
+            }, SERIALIZE_REVIEW_REQUESTS);
+        }
+        let mut output = String::with_capacity(100);
+        for user in pull.requested_reviewers {
+            output.push_str(&user.login);
+            output.push_str(NL);
+        }
+        for team in pull.requested_teams {
+            output.push_str(&format!("{}/{}", owner, team.slug));
+            output.push_str(NL);
+        }
+        Ok(output)
+    }
+
+    #[derive(Serialize)]
+    struct ReviewRequests {
+        users: Vec<String>,
+        teams: Vec<String>
+    }
+
+    const SERIALIZE_REVIEW_REQUESTS: &'static str = "Error serializing GitHub review request JSON.";
+
+    #[cfg(test)]
+    mod tests {
+    use evidence::json_ops;
+    use serde_json::Value;
+    use super::{format_output, review_request};
+
+        const PULL_REQUEST: &'static str = "{\"id\":1,\"number\":1347,\"state\":\"open\",\"title\":\"Amazing new feature\",\
+                                            \"url\":\"https://api.github.com/repos/octocat/Hello-World/pulls/1347\",\
+                                            \"html_url\":\"https://github.com/octocat/Hello-World/pull/1347\",\
+                                            \"user\":{\"login\":\"octocat\",\"id\":1},\
+                                            \"head\":{\"label\":\"octocat:new-topic\",\"ref\":\"new-topic\",\"sha\":\"6dcb09b\"},\
+                                            \"base\":{\"label\":\"octocat:master\",\"ref\":\"master\",\"sha\":\"6dcb09b\"},\
+                                            \"body\":null,\"mergeable\":null,\"mergeable_state\":null,\"merged_by\":null,\
+                                            \"requested_reviewers\":[{\"login\":\"other_user\",\"id\":2}],\
+                                            \"requested_teams\":[{\"name\":\"Justice League\",\"slug\":\"justice-league\"}],\
+                                            \"created_at\":\"2011-01-26T19:01:12Z\",\"updated_at\":\"2011-01-26T19:01:12Z\",\
+                                            \"closed_at\":null,\"merged_at\":null}";
+
+        #[test]
+        fn test_review_request_splits_teams() -> () {
+            let review_request = review_request(vec!["hubot".to_owned(), "octocat/justice-league".to_owned(),
+                                                     "other_user".to_owned()]);
+            assert_eq!(review_request.reviewers, vec!["hubot".to_owned(), "other_user".to_owned()]);
+            assert_eq!(review_request.team_reviewers, vec!["justice-league".to_owned()]);
+        }
+
+        #[test]
+        fn test_format_output() -> () {
+            let output = format_output("octocat", json_ops::from_str(PULL_REQUEST, "").unwrap(), false).unwrap();
+            let lines: Vec<&str> = output.trim().lines().collect();
+            assert_eq!(lines, vec!["other_user", "octocat/justice-league"]);
+        }
+
+        #[test]
+        fn test_format_output_json() -> () {
+            let output = format_output("octocat", json_ops::from_str(PULL_REQUEST, "").unwrap(), true).unwrap();
+            let value: Value = json_ops::from_str(&output, "").unwrap();
+            assert_eq!(value["users"][0], "other_user");
+            assert_eq!(value["teams"][0], "justice-league");
+        }
+    }
+}
+
+mod merge {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, repo_from_args, split_full_name};
+use error::{GhError, GhResult};
+use evidence::json_ops;
+use git_hub::{pulls, repos, Client};
+use git_hub::pulls::{MergePullRequest, MergeResult};
+use hyper::status::StatusCode;
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let client = client_from_args(matches)?;
+        let (owner, repo) = repo_from_args(matches, &client)?;
+        let number = matches.value_of("number").unwrap().parse().unwrap();
+        let merge = MergePullRequest {
+            commit_title: matches.value_of("title").map(|x| x.to_owned()),
+            commit_message: matches.value_of("message").map(|x| x.to_owned()),
+            sha: matches.value_of("sha").map(|x| x.to_owned()),
+            merge_method: matches.value_of("method").map(|x| x.to_owned())
+        };
+        let result = pulls::merge_pull_request(&owner, &repo, number, &merge, &client).map_err(explain_error)?;
+        let output = &format_output(number, &result, is_json(matches))?;
+        println!("{}", output.trim());
+        if matches.is_present("delete-branch") {
+            delete_head_branch(&owner, &repo, number, &client)?;
+        }
+        Ok(())
+    }
+
+    // The head branch may live in a fork, which is where it's deleted from.
+    // The default branch of the head repository is never deleted, as when
+    // main is merged into a release branch.
+    fn delete_head_branch(owner: &str, repo: &str, number: u32, client: &Client) -> GhResult<()> {
+        let pull = pulls::get_pull_request(owner, repo, number, client).map_err(super::explain_error)?;
+        let head_repo = match pull.head.repo {
+            Some(head_repo) => head_repo,
+            None            => return Ok(())
+        };
+        let (head_owner, head_name) = split_full_name(&head_repo.full_name)?;
+        let default_branch = match head_repo.default_branch {
+            Some(ref default_branch) => default_branch.clone(),
+            None                     => {
+                repos::get_repo(head_owner, head_name, client).map_err(super::explain_error)?.default_branch
+            }
+        };
+        if pull.head.ref_name == default_branch {
+            eprintln!("gh: not deleting {}, the default branch of {}", pull.head.label, head_repo.full_name);
+            return Ok(());
+        }
+        repos::delete_branch(head_owner, head_name, &pull.head.ref_name, client).map_err(super::explain_error)?;
+        println!("Deleted branch {}", pull.head.label);
+        Ok(())
+    }
+
+    // GitHub's message says why a merge was refused, e.g. "Pull Request is
+    // not mergeable" or "Squash merges are not allowed on this repository."
+    fn explain_error(error: GhError) -> GhError {
+        match error {
+            GhError::Api(StatusCode::MethodNotAllowed, e) => {
+                GhError::Http(StatusCode::MethodNotAllowed, format!("{} {}", NOT_MERGEABLE, e.message))
+            },
+            GhError::Api(StatusCode::Conflict, e) => {
+                GhError::Http(StatusCode::Conflict, format!("{} {}", HEAD_MODIFIED, e.message))
+            },
+            error => super::explain_error(error)
+        }
+    }
+
+    fn format_output(number: u32, result: &MergeResult, is_json: bool) -> GhResult<String> {
+        if is_json {
+            json_ops::to_pretty_json(result, SERIALIZE_MERGE_RESULT)
+        } else {
+            Ok(format!("Merged #{} as {}", number, result.sha))
+        }
+    }
+
+    const SERIALIZE_MERGE_RESULT: &'static str = "Error serializing GitHub Pull Request merge JSON.";
+
+    const NOT_MERGEABLE: &'static str = "405 Method Not Allowed. The Pull Request can't be merged:";
+    const HEAD_MODIFIED: &'static str = "409 Conflict. The head branch is no longer at --sha:";
+
+    #[cfg(test)]
+    mod tests {
+    use error::{ApiError, GhError};
+    use evidence::json_ops;
+    use hyper::status::StatusCode;
+    use super::{explain_error, format_output};
+
+        fn api_error(status: StatusCode, message: &str) -> GhError {
+            GhError::Api(status, ApiError {
+                message: message.to_owned(),
+                documentation_url: None,
+                errors: vec![]
+            })
+        }
+
+        #[test]
+        fn test_explain_error_not_mergeable() -> () {
+            let error = explain_error(api_error(StatusCode::MethodNotAllowed, "Pull Request is not mergeable"));
+            assert_eq!(error.to_string(), "405 Method Not Allowed. The Pull Request can't be merged: Pull Request is not mergeable");
+            assert_eq!(error.exit_code(), 5);
+        }
+
+        #[test]
+        fn test_explain_error_head_modified() -> () {
+            let error = explain_error(api_error(StatusCode::Conflict, "Head branch was modified. Review and try the merge again."));
+            assert_eq!(error.to_string(),
+                       "409 Conflict. The head branch is no longer at --sha: Head branch was modified. Review and try the merge again.");
+        }
+
+        #[test]
+        fn test_format_output() -> () {
+            let body = "{\"sha\":\"6dcb09b5b57875f334f61aebed695e2e4193db5e\",\"merged\":true,\
+                        \"message\":\"Pull Request successfully merged\"}";
+            assert_eq!(format_output(1347, &json_ops::from_str(body, "").unwrap(), false).unwrap(),
+                       "Merged #1347 as 6dcb09b5b57875f334f61aebed695e2e4193db5e");
+        }
+    }
+}
+
+mod checkout {
+use clap::ArgMatches;
+use commands::{client_from_args, repo_from_args};
+use error::{GhError, GhResult};
+use git;
+use git_hub::pulls;
+use super::explain_error;
+
+    // Fetches refs/pull/N/head, which GitHub keeps for every Pull Request
+    // including those from forks, from the checkout's remote for the
+    // repository, or its clone URL when there is no such remote. The local
+    // branch defaults to pr-N, as the head branch of a fork is often named
+    // like one of the checkout's own, main or master.
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let client = client_from_args(matches)?;
+        let (owner, repo) = repo_from_args(matches, &client)?;
+        let number: u32 = matches.value_of("number").unwrap().parse().unwrap();
+        let pull = pulls::get_pull_request(&owner, &repo, number, &client)
+                         .map_err(explain_error)?;
+        let host = git::web_host(client.config().api_url());
+        let remote = match git::remote_name(&owner, &repo, &host) {
+            Some(remote) => remote,
+            None         => match pull.base.repo {
+                Some(ref base) => base.clone_url.clone(),
+                None           => format!("https://{}/{}/{}.git", host, owner, repo)
+            }
+        };
+        let default_branch = format!("pr-{}", number);
+        let branch = matches.value_of("branch").unwrap_or(&default_branch);
+        let pull_ref = format!("refs/pull/{}/head", number);
+        // git refuses to fetch into the branch that is checked out, so that
+        // one is fast-forwarded instead, or reset with --force after the
+        // author force-pushed. Any other branch is simply overwritten.
+        if git::current_branch().as_ref().map(|x| x.as_str()) == Some(branch) {
+            git::run(&["fetch", &remote, &pull_ref])?;
+            if git::is_ancestor("HEAD", "FETCH_HEAD")? {
+                git::run(&["merge", "--ff-only", "FETCH_HEAD"])
+            } else if matches.is_present("force") {
+                git::run(&["reset", "--hard", "FETCH_HEAD"])
+            } else {
+                Err(GhError::Usage(format!("{} {}", branch, FORCE_PUSHED)))
+            }
+        } else {
+            git::run(&["fetch", &remote, &format!("+{}:{}", pull_ref, branch)])?;
+            git::run(&["checkout", branch])
+        }
+    }
+
+    const FORCE_PUSHED: &'static str = "can't be fast-forwarded to the Pull Request, which was force-pushed or has diverged from local commits. Pass --force to reset the branch to it, discarding those commits.";
 }
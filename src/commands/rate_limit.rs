@@ -0,0 +1,99 @@
+use clap::{App, ArgMatches, SubCommand};
+use commands;
+use commands::{client_from_args, is_json, NL, UNAUTHORIZED};
+use error::{GhError, GhResult};
+use evidence::json_ops;
+use git_hub::rate_limit;
+use git_hub::rate_limit::RateLimit;
+use hyper::status::StatusCode;
+
+pub fn SUBCOMMAND<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("rate-limit")
+                .about("Shows your GitHub API rate limit status for each resource.")
+                .version(version!())
+                .author("penland365 <Jeffrey.N.Davis@gmail.com>")
+                .arg(commands::format_arg())
+}
+
+pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+    let rate_limit = rate_limit::get_rate_limit(&client_from_args(matches)?).map_err(explain_error)?;
+    let output = &format_output(rate_limit, is_json(matches), rate_limit::now())?;
+    println!("{}", output.trim());
+    Ok(())
+}
+
+fn explain_error(error: GhError) -> GhError {
+    match error {
+        GhError::Api(StatusCode::Unauthorized, _) => GhError::Auth(StatusCode::Unauthorized, UNAUTHORIZED.to_owned()),
+        GhError::Api(StatusCode::NotFound, _)     => GhError::Http(StatusCode::NotFound, NOT_FOUND.to_owned()),
+        error                                     => error
+    }
+}
+
+fn format_output(rate_limit: RateLimit, is_json: bool, now: u64) -> GhResult<String> {
+    if is_json {
+        json_ops::to_pretty_json(&rate_limit, SERIALIZE_RATE_LIMIT)
+    } else {
+        let mut output = String::with_capacity(100);
+        let header = format!("{0: <25} {1: <10} {2: <10} {3: <10} {4}", "resource", "limit", "remaining", "used", "resets in");
+        output.push_str(&header);
+        output.push_str(NL);
+        for (name, resource) in &rate_limit.resources {
+            let used = match resource.used {
+                Some(used) => used,
+                None       => resource.limit.saturating_sub(resource.remaining)
+            };
+            let resets_in = if resource.reset > now { resource.reset - now } else { 0 };
+            let line = format!("{0: <25} {1: <10} {2: <10} {3: <10} {4}",
+                               name, resource.limit, resource.remaining, used, format!("{}s", resets_in));
+            output.push_str(&line);
+            output.push_str(NL);
+        }
+        Ok(output)
+    }
+}
+
+const SERIALIZE_RATE_LIMIT: &'static str = "Error serializing GitHub Rate Limit JSON.";
+
+const NOT_FOUND: &'static str = "404 Not Found. Rate limiting is not enabled on this GitHub Enterprise Server.";
+
+#[cfg(test)]
+mod tests {
+use error::{ApiError, GhError};
+use evidence::json_ops;
+use hyper::status::StatusCode;
+use super::{explain_error, format_output, NOT_FOUND};
+
+    #[test]
+    fn test_explain_error_not_found() -> () {
+        let error = GhError::Api(StatusCode::NotFound, ApiError {
+            message: "Not Found".to_owned(),
+            documentation_url: None,
+            errors: vec![]
+        });
+        assert_eq!(explain_error(error).to_string(), NOT_FOUND);
+    }
+
+    #[test]
+    fn test_format_output_table() -> () {
+        let body = "{\"resources\":{\
+                    \"core\":{\"limit\":5000,\"remaining\":4999,\"reset\":1372700873,\"used\":1},\
+                    \"search\":{\"limit\":30,\"remaining\":18,\"reset\":1372697452}},\
+                    \"rate\":{\"limit\":5000,\"remaining\":4999,\"reset\":1372700873}}";
+        let output = format_output(json_ops::from_str(body, "").unwrap(), false, 1372697400).unwrap();
+        let lines: Vec<&str> = output.trim().lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("core"));
+        assert!(lines[1].ends_with("3473s"));
+        assert!(lines[2].starts_with("search"));
+        assert!(lines[2].contains(" 12 "));
+    }
+
+    #[test]
+    fn test_format_output_remaining_over_limit() -> () {
+        let body = "{\"resources\":{\"core\":{\"limit\":60,\"remaining\":4999,\"reset\":1372700873}}}";
+        let output = format_output(json_ops::from_str(body, "").unwrap(), false, 1372697400).unwrap();
+        let lines: Vec<&str> = output.trim().lines().collect();
+        assert_eq!(lines[1].split_whitespace().nth(3), Some("0"));
+    }
+}
@@ -0,0 +1,470 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use commands;
+use error::{GhError, GhResult};
+use evidence::json_ops;
+use git_hub::repos::Repository;
+
+pub fn SUBCOMMAND<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("repo")
+                .about("List, View, Create, Fork, Archive and Delete GitHub Repositories.")
+                .version(version!())
+                .author("penland365 <Jeffrey.N.Davis@gmail.com>")
+                .subcommand(SubCommand::with_name("list")
+                                        .about("Lists Repositories of the credentialed user, another user, or an organization.")
+                                        .arg(Arg::with_name("user")
+                                             .short("u")
+                                             .long("user")
+                                             .help("Lists the public Repositories of this user.")
+                                             .value_name("login")
+                                             .takes_value(true)
+                                             .conflicts_with("org"))
+                                        .arg(Arg::with_name("org")
+                                             .short("o")
+                                             .long("org")
+                                             .help("Lists the Repositories of this organization.")
+                                             .value_name("org")
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("type")
+                                             .short("t")
+                                             .long("type")
+                                             .help("Filters Repositories by type. forks, sources and internal only apply to --org, private to --org and the credentialed user.")
+                                             .possible_values(&["all", "owner", "public", "private", "member", "forks", "sources", "internal"])
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("sort")
+                                             .short("s")
+                                             .long("sort")
+                                             .help("Orders Repositories by this property.")
+                                             .possible_values(&["created", "updated", "pushed", "full_name"])
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("visibility")
+                                             .long("visibility")
+                                             .help("Filters the credentialed user's Repositories by visibility.")
+                                             .possible_values(&["all", "public", "private"])
+                                             .takes_value(true)
+                                             .conflicts_with_all(&["user", "org"]))
+                                        .arg(commands::format_arg())
+                                        .args(&commands::pagination_args()))
+                .subcommand(SubCommand::with_name("view")
+                                        .about("Shows a single Repository.")
+                                        .arg(commands::format_arg()))
+                .subcommand(SubCommand::with_name("create")
+                                        .about("Creates a new Repository.")
+                                        .arg(Arg::with_name("name")
+                                                  .required(true)
+                                                  .help("The name of the new Repository."))
+                                        .arg(Arg::with_name("org")
+                                             .short("o")
+                                             .long("org")
+                                             .help("Creates the Repository in this organization instead of for the credentialed user.")
+                                             .value_name("org")
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("description")
+                                             .short("d")
+                                             .long("description")
+                                             .help("A short description of the Repository.")
+                                             .value_name("text")
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("homepage")
+                                             .long("homepage")
+                                             .help("A URL with more information about the Repository.")
+                                             .value_name("url")
+                                             .takes_value(true)
+                                             .conflicts_with("template"))
+                                        .arg(Arg::with_name("private")
+                                             .short("p")
+                                             .long("private")
+                                             .help("Makes the Repository private."))
+                                        .arg(Arg::with_name("init")
+                                             .long("init")
+                                             .help("Creates an initial commit with an empty README.")
+                                             .conflicts_with("template"))
+                                        .arg(Arg::with_name("template")
+                                             .long("template")
+                                             .help("Creates the Repository from this template Repository.")
+                                             .value_name("OWNER/REPO")
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("include-all-branches")
+                                             .long("include-all-branches")
+                                             .help("Copies every branch of the template, not just the default branch.")
+                                             .requires("template"))
+                                        .arg(commands::format_arg()))
+                .subcommand(SubCommand::with_name("fork")
+                                        .about("Forks a Repository for the credentialed user or an organization.")
+                                        .arg(Arg::with_name("org")
+                                             .short("o")
+                                             .long("org")
+                                             .help("Forks into this organization instead of for the credentialed user.")
+                                             .value_name("org")
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("name")
+                                             .short("n")
+                                             .long("name")
+                                             .help("Names the fork differently from the Repository.")
+                                             .value_name("name")
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("default-branch-only")
+                                             .long("default-branch-only")
+                                             .help("Only forks the default branch."))
+                                        .arg(commands::format_arg()))
+                .subcommand(SubCommand::with_name("archive")
+                                        .about("Archives a Repository, making it read-only.")
+                                        .arg(full_name_arg())
+                                        .arg(commands::format_arg()))
+                .subcommand(SubCommand::with_name("delete")
+                                        .about("Deletes a Repository. Requires the delete_repo scope.")
+                                        .arg(full_name_arg())
+                                        .arg(Arg::with_name("yes")
+                                             .short("y")
+                                             .long("yes")
+                                             .help("Deletes without asking for confirmation.")))
+}
+
+pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+    match matches.subcommand() {
+        ("list", Some(list_matches))       => list::handle(list_matches),
+        ("view", Some(view_matches))       => view::handle(view_matches),
+        ("create", Some(create_matches))   => create::handle(create_matches),
+        ("fork", Some(fork_matches))       => fork::handle(fork_matches),
+        ("archive", Some(archive_matches)) => archive::handle(archive_matches),
+        ("delete", Some(delete_matches))   => delete::handle(delete_matches),
+        ("", None)                         => {
+            println!("No subcommand was used for repo");
+            Ok(())
+        },
+        (_, _)                             => unreachable!()
+    }
+}
+
+// The repository archive and delete work on. Those are never inferred from
+// the current git checkout.
+fn full_name_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("full-name")
+        .help("The Repository, OWNER/REPO. Can also be given with --repo.")
+        .value_name("OWNER/REPO")
+}
+
+fn explain_error(error: GhError) -> GhError {
+    commands::explain_error(error, FORBIDDEN, NOT_FOUND)
+}
+
+// The output of commands that create or change a single Repository: its
+// full name and URL.
+fn format_repo(repo: Repository, is_json: bool) -> GhResult<String> {
+    if is_json {
+        json_ops::to_pretty_json(&repo, SERIALIZE_REPOSITORY)
+    } else {
+        Ok(format!("{0: <40} {1}", repo.full_name, repo.html_url))
+    }
+}
+
+fn visibility(repo: &Repository) -> &str {
+    match repo.visibility {
+        Some(ref visibility) => visibility,
+        None                 => if repo.private { "private" } else { "public" }
+    }
+}
+
+const SERIALIZE_REPOSITORY: &'static str = "Error serializing GitHub Repository JSON.";
+
+const FORBIDDEN: &'static str = "403 Forbidden. Does your OAuth token have sufficient scope? `repo` is required for private repositories, and `delete_repo` to delete one. See https://developer.github.com/v3/repos/";
+const NOT_FOUND: &'static str = "404 Not Found. Does the repository, user or organization exist, and can your credentials see it? See https://developer.github.com/v3/repos/";
+
+mod list {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, NL, pagination_from_args};
+use error::GhResult;
+use evidence::json_ops;
+use git_hub::repos;
+use git_hub::repos::{Repository, RepositoryFilters, RepositoryOwnerKind};
+use super::{explain_error, visibility};
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let owner = match (matches.value_of("user"), matches.value_of("org")) {
+            (Some(user), _) => RepositoryOwnerKind::User(user.to_owned()),
+            (_, Some(org))  => RepositoryOwnerKind::Org(org.to_owned()),
+            (None, None)    => RepositoryOwnerKind::AuthedUser
+        };
+        let filters = RepositoryFilters {
+            repo_type: matches.value_of("type").map(|x| x.to_owned()),
+            sort: matches.value_of("sort").map(|x| x.to_owned()),
+            visibility: matches.value_of("visibility").map(|x| x.to_owned())
+        };
+        let pagination = pagination_from_args(matches);
+        let repos = repos::list_repos(&owner, &filters, &client_from_args(matches)?, &pagination)
+                           .map_err(explain_error)?;
+        let output = &format_output(repos, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    fn format_output(repos: Vec<Repository>, is_json: bool) -> GhResult<String> {
+        if is_json {
+            json_ops::to_pretty_json(&repos, SERIALIZE_REPOSITORIES)
+        } else {
+            let mut output = String::with_capacity(100);
+            let header = format!("{0: <40} {1: <10} {2: <8} {3}", "name", "visibility", "stars", "description");
+            output.push_str(&header);
+            output.push_str(NL);
+            for repo in repos {
+                let mut name = repo.full_name.clone();
+                if repo.archived {
+                    name.push_str(" (archived)");
+                }
+                let line = format!("{0: <40} {1: <10} {2: <8} {3}",
+                                   name, visibility(&repo), repo.stargazers_count,
+                                   repo.description.as_ref().map(|x| x.as_str()).unwrap_or(""));
+                output.push_str(&line);
+                output.push_str(NL);
+            }
+            Ok(output)
+        }
+    }
+
+    const SERIALIZE_REPOSITORIES: &'static str = "Error serializing GitHub Repository JSON.";
+
+    #[cfg(test)]
+    mod tests {
+    use evidence::json_ops;
+    use super::format_output;
+    use super::super::tests::REPOSITORY;
+
+        #[test]
+        fn test_format_output_table() -> () {
+            let body = format!("[{}]", REPOSITORY);
+            let output = format_output(json_ops::from_str(&body, "").unwrap(), false).unwrap();
+            let lines: Vec<&str> = output.trim().lines().collect();
+            assert_eq!(lines.len(), 2);
+            assert!(lines[1].starts_with("octocat/Hello-World (archived)"));
+            assert!(lines[1].contains(" public "));
+            assert!(lines[1].ends_with("This your first repo!"));
+        }
+    }
+}
+
+mod view {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, NL, repo_from_args};
+use error::GhResult;
+use evidence::json_ops;
+use git_hub::repos;
+use git_hub::repos::Repository;
+use super::{explain_error, visibility, SERIALIZE_REPOSITORY};
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let client = client_from_args(matches)?;
+        let (owner, repo) = repo_from_args(matches, &client)?;
+        let repo  = repos::get_repo(&owner, &repo, &client).map_err(explain_error)?;
+        let output = &format_output(repo, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    fn format_output(repo: Repository, is_json: bool) -> GhResult<String> {
+        if is_json {
+            return json_ops::to_pretty_json(&repo, SERIALIZE_REPOSITORY);
+        }
+        let mut output = String::with_capacity(500);
+        output.push_str(&repo.full_name);
+        output.push_str(NL);
+        if let Some(ref description) = repo.description {
+            output.push_str(description);
+            output.push_str(NL);
+        }
+        output.push_str(NL);
+        let license = match repo.license {
+            Some(ref license) => license.name.as_str(),
+            None              => ""
+        };
+        let forked_from = match repo.parent {
+            Some(ref parent) => parent.full_name.as_str(),
+            None             => ""
+        };
+        let fields = vec![("visibility", visibility(&repo).to_owned()),
+                          ("default branch", repo.default_branch.clone()),
+                          ("language", repo.language.clone().unwrap_or_default()),
+                          ("license", license.to_owned()),
+                          ("topics", repo.topics.join(", ")),
+                          ("stars", repo.stargazers_count.to_string()),
+                          ("forks", repo.forks_count.to_string()),
+                          ("open issues", repo.open_issues_count.to_string()),
+                          ("archived", repo.archived.to_string()),
+                          ("template", repo.is_template.to_string()),
+                          ("forked from", forked_from.to_owned()),
+                          ("homepage", repo.homepage.clone().unwrap_or_default()),
+                          ("url", repo.html_url.clone()),
+                          ("clone url", repo.clone_url.clone()),
+                          ("ssh url", repo.ssh_url.clone())];
+        for (name, value) in fields {
+            if !value.is_empty() {
+                output.push_str(&format!("{0: <15} {1}", name, value));
+                output.push_str(NL);
+            }
+        }
+        Ok(output)
+    }
+
+    #[cfg(test)]
+    mod tests {
+    use evidence::json_ops;
+    use super::format_output;
+    use super::super::tests::REPOSITORY;
+
+        #[test]
+        fn test_format_output() -> () {
+            let output = format_output(json_ops::from_str(REPOSITORY, "").unwrap(), false).unwrap();
+            let lines: Vec<&str> = output.trim().lines().collect();
+            assert_eq!(lines[0], "octocat/Hello-World");
+            assert_eq!(lines[1], "This your first repo!");
+            assert_eq!(lines[3], "visibility      public");
+            assert_eq!(lines[4], "default branch  master");
+            // no language, license or topics
+            assert_eq!(lines[5], "stars           80");
+        }
+    }
+}
+
+mod create {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, split_full_name};
+use error::GhResult;
+use git_hub::{repos, users};
+use git_hub::repos::{NewRepository, NewRepositoryFromTemplate};
+use super::{explain_error, format_repo};
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let name = matches.value_of("name").unwrap();
+        let org = matches.value_of("org");
+        let description = matches.value_of("description").map(|x| x.to_owned());
+        let private = if matches.is_present("private") { Some(true) } else { None };
+        let client = client_from_args(matches)?;
+        let repo = match matches.value_of("template") {
+            Some(template) => {
+                let (template_owner, template_repo) = split_full_name(template)?;
+                // The configured username may be blank, with a token from
+                // the environment, or not the token's, so ask GitHub
+                let owner = match org {
+                    Some(org) => org.to_owned(),
+                    None      => users::get_authed_user_profile(&client).map_err(explain_error)?.login
+                };
+                let repository = NewRepositoryFromTemplate {
+                    owner: owner,
+                    name: name.to_owned(),
+                    description: description,
+                    private: private,
+                    include_all_branches: if matches.is_present("include-all-branches") { Some(true) } else { None }
+                };
+                repos::create_repo_from_template(template_owner, template_repo, &repository, &client)
+            },
+            None => {
+                let repository = NewRepository {
+                    name: name.to_owned(),
+                    description: description,
+                    homepage: matches.value_of("homepage").map(|x| x.to_owned()),
+                    private: private,
+                    auto_init: if matches.is_present("init") { Some(true) } else { None }
+                };
+                repos::create_repo(org, &repository, &client)
+            }
+        }.map_err(explain_error)?;
+        let output = &format_repo(repo, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+}
+
+mod fork {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, repo_from_args};
+use error::GhResult;
+use git_hub::repos;
+use git_hub::repos::NewFork;
+use super::{explain_error, format_repo};
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let client = client_from_args(matches)?;
+        let (owner, repo) = repo_from_args(matches, &client)?;
+        let fork = NewFork {
+            organization: matches.value_of("org").map(|x| x.to_owned()),
+            name: matches.value_of("name").map(|x| x.to_owned()),
+            default_branch_only: if matches.is_present("default-branch-only") { Some(true) } else { None }
+        };
+        let repo = repos::fork_repo(&owner, &repo, &fork, &client).map_err(explain_error)?;
+        let output = &format_repo(repo, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+}
+
+mod archive {
+use clap::ArgMatches;
+use commands::{client_from_args, explicit_repo_from_args, is_json};
+use error::GhResult;
+use git_hub::repos;
+use git_hub::repos::EditRepository;
+use super::{explain_error, format_repo};
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let (owner, repo) = explicit_repo_from_args(matches)?;
+        let edit = EditRepository {
+            archived: Some(true)
+        };
+        let repo = repos::edit_repo(&owner, &repo, &edit, &client_from_args(matches)?).map_err(explain_error)?;
+        let output = &format_repo(repo, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+}
+
+mod delete {
+use clap::ArgMatches;
+use commands::{client_from_args, confirm, explicit_repo_from_args};
+use error::{GhError, GhResult};
+use git_hub::repos;
+use super::explain_error;
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let (owner, repo) = explicit_repo_from_args(matches)?;
+        let full_name = format!("{}/{}", owner, repo);
+        if !matches.is_present("yes") {
+            let prompt = format!("This permanently deletes {}, its issues, pull requests and wiki.", full_name);
+            if !confirm(&prompt, &full_name)? {
+                return Err(GhError::Usage(CANCELLED.to_owned()));
+            }
+        }
+        repos::delete_repo(&owner, &repo, &client_from_args(matches)?).map_err(explain_error)?;
+        println!("Deleted {}", full_name);
+        Ok(())
+    }
+
+    const CANCELLED: &'static str = "Deletion cancelled, the confirmation did not match.";
+}
+
+#[cfg(test)]
+mod tests {
+use error::{ApiError, GhError};
+use hyper::status::StatusCode;
+use super::{explain_error, FORBIDDEN};
+
+    pub const REPOSITORY: &'static str = "{\"id\":1296269,\"name\":\"Hello-World\",\"full_name\":\"octocat/Hello-World\",\
+                                          \"owner\":{\"login\":\"octocat\",\"id\":1,\"type\":\"User\"},\
+                                          \"private\":false,\"description\":\"This your first repo!\",\"fork\":false,\
+                                          \"archived\":true,\
+                                          \"url\":\"https://api.github.com/repos/octocat/Hello-World\",\
+                                          \"html_url\":\"https://github.com/octocat/Hello-World\",\
+                                          \"clone_url\":\"https://github.com/octocat/Hello-World.git\",\
+                                          \"ssh_url\":\"git@github.com:octocat/Hello-World.git\",\
+                                          \"homepage\":null,\"language\":null,\"default_branch\":\"master\",\
+                                          \"stargazers_count\":80,\"forks_count\":9,\"open_issues_count\":0,\
+                                          \"created_at\":\"2011-01-26T19:01:12Z\",\"updated_at\":\"2011-01-26T19:14:43Z\",\
+                                          \"pushed_at\":\"2011-01-26T19:06:43Z\"}";
+
+    #[test]
+    fn test_explain_error_forbidden() -> () {
+        let error = GhError::Api(StatusCode::Forbidden, ApiError {
+            message: "Must have admin rights to Repository.".to_owned(),
+            documentation_url: None,
+            errors: vec![]
+        });
+        assert_eq!(explain_error(error).to_string(), FORBIDDEN);
+    }
+}
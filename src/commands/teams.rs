@@ -0,0 +1,679 @@
+use clap::{App, Arg, ArgGroup, ArgMatches, SubCommand};
+use commands;
+use error::{GhError, GhResult};
+
+// gh orgs teams, kept apart from orgs.rs for its size
+pub fn SUBCOMMAND<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("teams")
+                .about("Lists, creates, edits and deletes an Organization's teams, their members and repositories.")
+                .subcommand(SubCommand::with_name("list")
+                                        .about("Lists the teams of an Organization.")
+                                        .arg(org_arg())
+                                        .arg(commands::format_arg())
+                                        .args(&commands::pagination_args()))
+                .subcommand(SubCommand::with_name("view")
+                                        .about("Shows a team, with its parent and child teams.")
+                                        .arg(org_arg())
+                                        .arg(team_arg())
+                                        .arg(commands::format_arg()))
+                .subcommand(SubCommand::with_name("create")
+                                        .about("Creates a team. Requires the admin:org scope.")
+                                        .arg(org_arg())
+                                        .arg(Arg::with_name("name")
+                                             .required(true)
+                                             .help("The name of the team."))
+                                        .arg(description_arg())
+                                        .arg(privacy_arg())
+                                        .arg(parent_arg())
+                                        .arg(Arg::with_name("maintainer")
+                                             .short("m")
+                                             .long("maintainer")
+                                             .help("Makes this member a maintainer of the team. May be repeated.")
+                                             .value_name("login")
+                                             .takes_value(true)
+                                             .multiple(true)
+                                             .number_of_values(1))
+                                        .arg(Arg::with_name("repository")
+                                             .long("repository")
+                                             .help("Gives the team access to this repository. May be repeated.")
+                                             .value_name("OWNER/REPO")
+                                             .takes_value(true)
+                                             .multiple(true)
+                                             .number_of_values(1))
+                                        .arg(commands::format_arg()))
+                .subcommand(SubCommand::with_name("edit")
+                                        .about("Edits a team's name, description, privacy or parent. Requires the admin:org scope.")
+                                        .arg(org_arg())
+                                        .arg(team_arg())
+                                        .arg(Arg::with_name("name")
+                                             .short("n")
+                                             .long("name")
+                                             .help("Renames the team, which changes its slug too.")
+                                             .value_name("name")
+                                             .takes_value(true))
+                                        .arg(description_arg())
+                                        .arg(privacy_arg())
+                                        .arg(parent_arg())
+                                        .arg(Arg::with_name("no-parent")
+                                             .long("no-parent")
+                                             .help("Moves the team to the top of the Organization."))
+                                        .group(ArgGroup::with_name("nesting")
+                                               .args(&["parent", "no-parent"]))
+                                        .arg(commands::format_arg()))
+                .subcommand(SubCommand::with_name("delete")
+                                        .about("Deletes a team and every team nested under it. Requires the admin:org scope.")
+                                        .arg(org_arg())
+                                        .arg(team_arg())
+                                        .arg(Arg::with_name("yes")
+                                             .short("y")
+                                             .long("yes")
+                                             .help("Deletes without asking for confirmation.")))
+                .subcommand(SubCommand::with_name("members")
+                                        .about("Lists, adds and removes the members of a team.")
+                                        .subcommand(SubCommand::with_name("list")
+                                                                .about("Lists the members of a team and of the teams nested under it.")
+                                                                .arg(org_arg())
+                                                                .arg(team_arg())
+                                                                .arg(Arg::with_name("role")
+                                                                     .short("r")
+                                                                     .long("role")
+                                                                     .help("Filters members by role.")
+                                                                     .possible_values(&["all", "maintainer", "member"])
+                                                                     .takes_value(true))
+                                                                .arg(commands::format_arg())
+                                                                .args(&commands::pagination_args()))
+                                        .subcommand(SubCommand::with_name("add")
+                                                                .about("Adds a user to a team, or changes their role. Users outside the Organization are invited to it.")
+                                                                .arg(org_arg())
+                                                                .arg(team_arg())
+                                                                .arg(username_arg())
+                                                                .arg(Arg::with_name("role")
+                                                                     .short("r")
+                                                                     .long("role")
+                                                                     .help("The role of the user in the team. Defaults to member, an existing member keeps their role.")
+                                                                     .possible_values(&["maintainer", "member"])
+                                                                     .takes_value(true))
+                                                                .arg(commands::format_arg()))
+                                        .subcommand(SubCommand::with_name("remove")
+                                                                .about("Removes a user from a team.")
+                                                                .arg(org_arg())
+                                                                .arg(team_arg())
+                                                                .arg(username_arg())))
+                .subcommand(SubCommand::with_name("repos")
+                                        .about("Lists the repositories a team has access to, and grants or revokes that access.")
+                                        .subcommand(SubCommand::with_name("list")
+                                                                .about("Lists the repositories a team has access to.")
+                                                                .arg(org_arg())
+                                                                .arg(team_arg())
+                                                                .arg(commands::format_arg())
+                                                                .args(&commands::pagination_args()))
+                                        .subcommand(SubCommand::with_name("add")
+                                                                .about("Gives a team access to a repository, or changes its permission.")
+                                                                .arg(org_arg())
+                                                                .arg(team_arg())
+                                                                .arg(full_name_arg())
+                                                                .arg(Arg::with_name("permission")
+                                                                     .short("p")
+                                                                     .long("permission")
+                                                                     .help("The team's permission on the repository. Defaults to the team's default permission, existing access is kept.")
+                                                                     .possible_values(&["pull", "triage", "push", "maintain", "admin"])
+                                                                     .takes_value(true)))
+                                        .subcommand(SubCommand::with_name("remove")
+                                                                .about("Revokes a team's access to a repository.")
+                                                                .arg(org_arg())
+                                                                .arg(team_arg())
+                                                                .arg(full_name_arg())))
+}
+
+pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+    match matches.subcommand() {
+        ("list", Some(list_matches))       => list::handle(list_matches),
+        ("view", Some(view_matches))       => view::handle(view_matches),
+        ("create", Some(create_matches))   => create::handle(create_matches),
+        ("edit", Some(edit_matches))       => edit::handle(edit_matches),
+        ("delete", Some(delete_matches))   => delete::handle(delete_matches),
+        ("members", Some(members_matches)) => members::handle(members_matches),
+        ("repos", Some(repos_matches))     => repos::handle(repos_matches),
+        ("", None)                         => {
+            println!("No subcommand was used for orgs teams");
+            Ok(())
+        },
+        (_, _)                             => unreachable!()
+    }
+}
+
+fn org_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("org")
+        .required(true)
+        .help("The login of the Organization.")
+}
+
+fn team_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("team")
+        .required(true)
+        .help("The slug of the team, its name in lower case with dashes for spaces.")
+}
+
+fn username_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("username")
+        .required(true)
+        .help("The login of the user.")
+}
+
+fn full_name_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("full-name")
+        .required(true)
+        .value_name("OWNER/REPO")
+        .help("The repository.")
+}
+
+fn description_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("description")
+        .short("d")
+        .long("description")
+        .help("A short description of the team.")
+        .value_name("text")
+        .takes_value(true)
+}
+
+fn privacy_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("privacy")
+        .long("privacy")
+        .help("closed teams are visible to every member of the Organization, secret ones only to their members. Nested teams must be closed.")
+        .possible_values(&["secret", "closed"])
+        .takes_value(true)
+}
+
+fn parent_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("parent")
+        .long("parent")
+        .help("Nests the team under this one.")
+        .value_name("team")
+        .takes_value(true)
+}
+
+fn explain_error(error: GhError) -> GhError {
+    commands::explain_error(error, FORBIDDEN, NOT_FOUND)
+}
+
+const FORBIDDEN: &'static str = "403 Forbidden. Changing a team needs the admin:org scope and an owner's or the team maintainer's credentials. See https://developer.github.com/v3/teams/";
+const NOT_FOUND: &'static str = "404 Not Found. Does the team exist, and can your credentials see it? Teams are named by their slug. See https://developer.github.com/v3/teams/";
+
+mod list {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, NL, pagination_from_args};
+use error::GhResult;
+use evidence::json_ops;
+use git_hub::teams;
+use git_hub::teams::Team;
+use super::explain_error;
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let pagination = pagination_from_args(matches);
+        let teams = teams::list_teams(matches.value_of("org").unwrap(), &client_from_args(matches)?, &pagination)
+                          .map_err(explain_error)?;
+        let output = &format_output(teams, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    pub fn format_output(teams: Vec<Team>, is_json: bool) -> GhResult<String> {
+        if is_json {
+            return json_ops::to_pretty_json(&teams, SERIALIZE_TEAMS);
+        }
+        let mut output = String::with_capacity(100);
+        output.push_str(&format!("{0: <25} {1: <8} {2: <25} {3}", "slug", "privacy", "parent", "description"));
+        output.push_str(NL);
+        for team in teams {
+            let parent = team.parent.map(|x| x.slug).unwrap_or(String::new());
+            let line = format!("{0: <25} {1: <8} {2: <25} {3}",
+                               team.slug,
+                               team.privacy.unwrap_or(String::new()),
+                               parent,
+                               team.description.unwrap_or(String::new()));
+            output.push_str(&line);
+            output.push_str(NL);
+        }
+        Ok(output)
+    }
+
+    const SERIALIZE_TEAMS: &'static str = "Error serializing GitHub Teams JSON.";
+
+    #[cfg(test)]
+    mod tests {
+    use evidence::json_ops;
+    use super::format_output;
+
+        #[test]
+        fn test_format_output() -> () {
+            let body = "[{\"id\":1,\"name\":\"Justice League\",\"slug\":\"justice-league\",\
+                        \"description\":\"A great team.\",\"privacy\":\"closed\",\"permission\":\"pull\",\
+                        \"url\":\"https://api.github.com/teams/1\",\"parent\":null},\
+                        {\"id\":2,\"name\":\"Bat Family\",\"slug\":\"bat-family\",\"description\":null,\
+                        \"privacy\":\"closed\",\"permission\":\"pull\",\"url\":\"https://api.github.com/teams/2\",\
+                        \"parent\":{\"id\":1,\"name\":\"Justice League\",\"slug\":\"justice-league\"}}]";
+            let output = format_output(json_ops::from_str(body, "").unwrap(), false).unwrap();
+            let lines: Vec<&str> = output.trim().lines().collect();
+            assert_eq!(lines[1], "justice-league            closed                             A great team.");
+            assert_eq!(lines[2], "bat-family                closed   justice-league");
+        }
+    }
+}
+
+mod view {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, NL};
+use error::GhResult;
+use evidence::json_ops;
+use git_hub::pagination::Pagination;
+use git_hub::teams;
+use git_hub::teams::Team;
+use super::explain_error;
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let org = matches.value_of("org").unwrap();
+        let slug = matches.value_of("team").unwrap();
+        let client = client_from_args(matches)?;
+        let team = teams::get_team(org, slug, &client).map_err(explain_error)?;
+        if is_json(matches) {
+            println!("{}", json_ops::to_pretty_json(&team, SERIALIZE_TEAM)?.trim());
+            return Ok(());
+        }
+        let children = teams::list_child_teams(org, slug, &client, &Pagination::default()).map_err(explain_error)?;
+        println!("{}", format_output(&team, &children).trim());
+        Ok(())
+    }
+
+    fn format_output(team: &Team, children: &[Team]) -> String {
+        let mut fields: Vec<(&str, String)> = vec![];
+        fields.push(("slug", team.slug.clone()));
+        if let Some(ref description) = team.description {
+            fields.push(("description", description.clone()));
+        }
+        if let Some(ref privacy) = team.privacy {
+            fields.push(("privacy", privacy.clone()));
+        }
+        fields.push(("permission", team.permission.clone()));
+        if let Some(ref parent) = team.parent {
+            fields.push(("parent", parent.slug.clone()));
+        }
+        if !children.is_empty() {
+            let slugs: Vec<&str> = children.iter().map(|x| x.slug.as_str()).collect();
+            fields.push(("children", slugs.join(", ")));
+        }
+        if let Some(members_count) = team.members_count {
+            fields.push(("members", members_count.to_string()));
+        }
+        if let Some(repos_count) = team.repos_count {
+            fields.push(("repos", repos_count.to_string()));
+        }
+        if let Some(ref html_url) = team.html_url {
+            fields.push(("url", html_url.clone()));
+        }
+
+        let mut output = String::with_capacity(300);
+        output.push_str(&team.name);
+        output.push_str(NL);
+        for (name, value) in fields {
+            output.push_str(&format!("{0: <12} {1}", name, value));
+            output.push_str(NL);
+        }
+        output
+    }
+
+    const SERIALIZE_TEAM: &'static str = "Error serializing GitHub Team JSON.";
+
+    #[cfg(test)]
+    mod tests {
+    use evidence::json_ops;
+    use super::format_output;
+
+        #[test]
+        fn test_format_output() -> () {
+            let team = "{\"id\":2,\"name\":\"Bat Family\",\"slug\":\"bat-family\",\"description\":null,\
+                        \"privacy\":\"closed\",\"permission\":\"pull\",\"url\":\"https://api.github.com/teams/2\",\
+                        \"html_url\":\"https://github.com/orgs/dc/teams/bat-family\",\
+                        \"parent\":{\"id\":1,\"name\":\"Justice League\",\"slug\":\"justice-league\"},\
+                        \"members_count\":3,\"repos_count\":10,\"created_at\":\"2017-07-14T16:53:42Z\",\
+                        \"updated_at\":\"2017-08-17T12:37:15Z\"}";
+            let child = "{\"id\":3,\"name\":\"Robins\",\"slug\":\"robins\",\"description\":null,\
+                         \"privacy\":\"closed\",\"permission\":\"pull\",\"url\":\"https://api.github.com/teams/3\"}";
+            let output = format_output(&json_ops::from_str(team, "").unwrap(), &[json_ops::from_str(child, "").unwrap()]);
+            assert_eq!(output.lines().collect::<Vec<&str>>(),
+                       vec!["Bat Family",
+                            "slug         bat-family",
+                            "privacy      closed",
+                            "permission   pull",
+                            "parent       justice-league",
+                            "children     robins",
+                            "members      3",
+                            "repos        10",
+                            "url          https://github.com/orgs/dc/teams/bat-family"]);
+        }
+    }
+}
+
+mod create {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, split_full_name, values};
+use error::GhResult;
+use evidence::json_ops;
+use git_hub::teams;
+use git_hub::teams::NewTeam;
+use super::explain_error;
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let org = matches.value_of("org").unwrap();
+        let client = client_from_args(matches)?;
+        let repo_names = values(matches, "repository");
+        for full_name in &repo_names {
+            split_full_name(full_name)?;
+        }
+        let parent_team_id = match matches.value_of("parent") {
+            Some(parent) => Some(teams::get_team(org, parent, &client).map_err(explain_error)?.id),
+            None         => None
+        };
+        let team = NewTeam {
+            name: matches.value_of("name").unwrap().to_owned(),
+            description: matches.value_of("description").map(|x| x.to_owned()),
+            maintainers: values(matches, "maintainer"),
+            repo_names: repo_names,
+            privacy: matches.value_of("privacy").map(|x| x.to_owned()),
+            parent_team_id: parent_team_id
+        };
+        let team = teams::create_team(org, &team, &client).map_err(explain_error)?;
+        if is_json(matches) {
+            println!("{}", json_ops::to_pretty_json(&team, SERIALIZE_TEAM)?.trim());
+        } else {
+            println!("{0: <25} {1}", team.slug, team.html_url.unwrap_or(team.url));
+        }
+        Ok(())
+    }
+
+    const SERIALIZE_TEAM: &'static str = "Error serializing GitHub Team JSON.";
+}
+
+mod edit {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json};
+use error::{GhError, GhResult};
+use evidence::json_ops;
+use git_hub::teams;
+use git_hub::teams::EditTeam;
+use super::explain_error;
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let org = matches.value_of("org").unwrap();
+        let slug = matches.value_of("team").unwrap();
+        let changes = ["name", "description", "privacy", "parent", "no-parent"];
+        if !changes.iter().any(|x| matches.is_present(x)) {
+            return Err(GhError::Usage(NOTHING_TO_EDIT.to_owned()));
+        }
+        let client = client_from_args(matches)?;
+        // GitHub wants the name with every edit, even when it stays the same
+        let name = match matches.value_of("name") {
+            Some(name) => name.to_owned(),
+            None       => teams::get_team(org, slug, &client).map_err(explain_error)?.name
+        };
+        let parent_team_id = match matches.value_of("parent") {
+            Some(parent) => Some(Some(teams::get_team(org, parent, &client).map_err(explain_error)?.id)),
+            None if matches.is_present("no-parent") => Some(None),
+            None         => None
+        };
+        let edit = EditTeam {
+            name: name,
+            description: matches.value_of("description").map(|x| x.to_owned()),
+            privacy: matches.value_of("privacy").map(|x| x.to_owned()),
+            parent_team_id: parent_team_id
+        };
+        let team = teams::edit_team(org, slug, &edit, &client).map_err(explain_error)?;
+        if is_json(matches) {
+            println!("{}", json_ops::to_pretty_json(&team, SERIALIZE_TEAM)?.trim());
+        } else {
+            println!("{0: <25} {1}", team.slug, team.html_url.unwrap_or(team.url));
+        }
+        Ok(())
+    }
+
+    const NOTHING_TO_EDIT: &'static str = "Nothing to edit. Pass at least one change, see gh orgs teams edit --help.";
+
+    const SERIALIZE_TEAM: &'static str = "Error serializing GitHub Team JSON.";
+}
+
+mod delete {
+use clap::ArgMatches;
+use commands::{client_from_args, confirm};
+use error::{GhError, GhResult};
+use git_hub::teams;
+use super::explain_error;
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let org = matches.value_of("org").unwrap();
+        let slug = matches.value_of("team").unwrap();
+        if !matches.is_present("yes") {
+            let prompt = format!("This permanently deletes the {} team of {}, and every team nested under it.", slug, org);
+            if !confirm(&prompt, slug)? {
+                return Err(GhError::Usage(CANCELLED.to_owned()));
+            }
+        }
+        teams::delete_team(org, slug, &client_from_args(matches)?).map_err(explain_error)?;
+        println!("Deleted {}/{}", org, slug);
+        Ok(())
+    }
+
+    const CANCELLED: &'static str = "Deletion cancelled, the confirmation did not match.";
+}
+
+mod members {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, NL, pagination_from_args};
+use error::{GhError, GhResult};
+use evidence::json_ops;
+use git_hub::orgs::OrgMember;
+use git_hub::teams;
+use hyper::status::StatusCode;
+use super::explain_error;
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        match matches.subcommand() {
+            ("list", Some(list_matches))     => list(list_matches),
+            ("add", Some(add_matches))       => add(add_matches),
+            ("remove", Some(remove_matches)) => remove(remove_matches),
+            ("", None)                       => {
+                println!("No subcommand was used for orgs teams members");
+                Ok(())
+            },
+            (_, _)                           => unreachable!()
+        }
+    }
+
+    fn list(matches: &ArgMatches) -> GhResult<()> {
+        let pagination = pagination_from_args(matches);
+        let members = teams::list_team_members(matches.value_of("org").unwrap(),
+                                               matches.value_of("team").unwrap(),
+                                               matches.value_of("role"),
+                                               &client_from_args(matches)?,
+                                               &pagination).map_err(explain_error)?;
+        let output = &format_members(members, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    fn add(matches: &ArgMatches) -> GhResult<()> {
+        let (org, team) = (matches.value_of("org").unwrap(), matches.value_of("team").unwrap());
+        let username = matches.value_of("username").unwrap();
+        let client = client_from_args(matches)?;
+        let role = matches.value_of("role");
+        // Without --role an existing member is left as they are, as adding
+        // them again would demote a maintainer
+        let existing = match role {
+            Some(_) => None,
+            None    => match teams::get_team_membership(org, team, username, &client) {
+                Ok(membership)                             => Some(membership),
+                Err(GhError::Api(StatusCode::NotFound, _)) => None,
+                Err(e)                                     => return Err(explain_error(e))
+            }
+        };
+        let membership = match existing {
+            Some(membership) => membership,
+            None             => teams::set_team_membership(org, team, username, role, &client).map_err(explain_error)?
+        };
+        if is_json(matches) {
+            println!("{}", json_ops::to_pretty_json(&membership, SERIALIZE_MEMBERSHIP)?.trim());
+        } else {
+            println!("{0: <25} {1: <10} {2}", username, membership.role, membership.state);
+        }
+        Ok(())
+    }
+
+    fn remove(matches: &ArgMatches) -> GhResult<()> {
+        let team = matches.value_of("team").unwrap();
+        let username = matches.value_of("username").unwrap();
+        teams::remove_team_membership(matches.value_of("org").unwrap(), team, username, &client_from_args(matches)?)
+              .map_err(explain_error)?;
+        println!("Removed {} from {}", username, team);
+        Ok(())
+    }
+
+    fn format_members(members: Vec<OrgMember>, is_json: bool) -> GhResult<String> {
+        if is_json {
+            return json_ops::to_pretty_json(&members, SERIALIZE_MEMBERS);
+        }
+        let mut output = String::with_capacity(100);
+        output.push_str(&format!("{0: <25} {1}", "login", "id"));
+        output.push_str(NL);
+        for member in members {
+            output.push_str(&format!("{0: <25} {1}", member.login, member.id));
+            output.push_str(NL);
+        }
+        Ok(output)
+    }
+
+    const SERIALIZE_MEMBERS: &'static str = "Error serializing GitHub Team members JSON.";
+    const SERIALIZE_MEMBERSHIP: &'static str = "Error serializing GitHub Team membership JSON.";
+}
+
+mod repos {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, NL, pagination_from_args, split_full_name};
+use error::{GhError, GhResult};
+use evidence::json_ops;
+use git_hub::teams;
+use git_hub::teams::{TeamRepository, TeamRepositoryPermissions};
+use hyper::status::StatusCode;
+use super::explain_error;
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        match matches.subcommand() {
+            ("list", Some(list_matches))     => list(list_matches),
+            ("add", Some(add_matches))       => add(add_matches),
+            ("remove", Some(remove_matches)) => remove(remove_matches),
+            ("", None)                       => {
+                println!("No subcommand was used for orgs teams repos");
+                Ok(())
+            },
+            (_, _)                           => unreachable!()
+        }
+    }
+
+    fn list(matches: &ArgMatches) -> GhResult<()> {
+        let pagination = pagination_from_args(matches);
+        let repos = teams::list_team_repos(matches.value_of("org").unwrap(),
+                                           matches.value_of("team").unwrap(),
+                                           &client_from_args(matches)?,
+                                           &pagination).map_err(explain_error)?;
+        let output = &format_output(repos, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    fn add(matches: &ArgMatches) -> GhResult<()> {
+        let team = matches.value_of("team").unwrap();
+        let full_name = matches.value_of("full-name").unwrap();
+        let (owner, repo) = split_full_name(full_name)?;
+        let org = matches.value_of("org").unwrap();
+        let client = client_from_args(matches)?;
+        match matches.value_of("permission") {
+            Some(permission) => {
+                teams::add_team_repo(org, team, owner, repo, Some(permission), &client).map_err(explain_error)?;
+                println!("Gave {} {} access to {}", team, permission, full_name);
+            },
+            // Without --permission existing access is left as it is, as
+            // adding the repository again would reset it to the default
+            None             => match teams::get_team_repo(org, team, owner, repo, &client) {
+                Ok(existing)                               => {
+                    println!("{} already has {} access to {}", team, permission(&existing.permissions), full_name);
+                },
+                Err(GhError::Api(StatusCode::NotFound, _)) => {
+                    teams::add_team_repo(org, team, owner, repo, None, &client).map_err(explain_error)?;
+                    println!("Gave {} access to {}", team, full_name);
+                },
+                Err(e)                                     => return Err(explain_error(e))
+            }
+        }
+        Ok(())
+    }
+
+    fn remove(matches: &ArgMatches) -> GhResult<()> {
+        let team = matches.value_of("team").unwrap();
+        let full_name = matches.value_of("full-name").unwrap();
+        let (owner, repo) = split_full_name(full_name)?;
+        teams::remove_team_repo(matches.value_of("org").unwrap(), team, owner, repo, &client_from_args(matches)?)
+              .map_err(explain_error)?;
+        println!("Removed {} from {}", full_name, team);
+        Ok(())
+    }
+
+    // The strongest of a team's permissions on a repository
+    fn permission(permissions: &TeamRepositoryPermissions) -> &'static str {
+        if permissions.admin {
+            "admin"
+        } else if permissions.maintain {
+            "maintain"
+        } else if permissions.push {
+            "push"
+        } else if permissions.triage {
+            "triage"
+        } else {
+            "pull"
+        }
+    }
+
+    fn format_output(repos: Vec<TeamRepository>, is_json: bool) -> GhResult<String> {
+        if is_json {
+            return json_ops::to_pretty_json(&repos, SERIALIZE_REPOSITORIES);
+        }
+        let mut output = String::with_capacity(100);
+        output.push_str(&format!("{0: <40} {1: <10} {2}", "repository", "permission", "private"));
+        output.push_str(NL);
+        for repo in repos {
+            output.push_str(&format!("{0: <40} {1: <10} {2}", repo.full_name, permission(&repo.permissions), repo.private));
+            output.push_str(NL);
+        }
+        Ok(output)
+    }
+
+    const SERIALIZE_REPOSITORIES: &'static str = "Error serializing GitHub Team repositories JSON.";
+
+    #[cfg(test)]
+    mod tests {
+    use git_hub::teams::TeamRepositoryPermissions;
+    use super::permission;
+
+        #[test]
+        fn test_permission() -> () {
+            let mut permissions = TeamRepositoryPermissions {
+                admin: false,
+                maintain: false,
+                push: true,
+                triage: true,
+                pull: true
+            };
+            assert_eq!(permission(&permissions), "push");
+            permissions.push = false;
+            assert_eq!(permission(&permissions), "triage");
+            permissions.maintain = true;
+            assert_eq!(permission(&permissions), "maintain");
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use commands;
+use error::{GhError, GhResult};
+
+pub fn SUBCOMMAND<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("user")
+                .about("View GitHub Users and their followers, Edit your profile")
+                .version(version!())
+                .author("penland365 <Jeffrey.N.Davis@gmail.com>")
+                .subcommand(SubCommand::with_name("view")
+                                        .about("Shows a user's profile, the credentialed user's when no login is given.")
+                                        .arg(login_arg())
+                                        .arg(commands::format_arg()))
+                .subcommand(SubCommand::with_name("followers")
+                                        .about("Lists a user's followers, the credentialed user's when no login is given.")
+                                        .arg(login_arg())
+                                        .arg(commands::format_arg())
+                                        .args(&commands::pagination_args()))
+                .subcommand(SubCommand::with_name("following")
+                                        .about("Lists the users a user follows, the credentialed user when no login is given.")
+                                        .arg(login_arg())
+                                        .arg(commands::format_arg())
+                                        .args(&commands::pagination_args()))
+                .subcommand(SubCommand::with_name("edit")
+                                        .about("Edits the credentialed user's profile. An empty value clears a field. Requires the user scope.")
+                                        .arg(Arg::with_name("name")
+                                             .short("n")
+                                             .long("name")
+                                             .help("Your name.")
+                                             .value_name("name")
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("bio")
+                                             .short("b")
+                                             .long("bio")
+                                             .help("A short biography.")
+                                             .value_name("text")
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("location")
+                                             .short("l")
+                                             .long("location")
+                                             .help("Where you are.")
+                                             .value_name("location")
+                                             .takes_value(true))
+                                        .arg(Arg::with_name("blog")
+                                             .long("blog")
+                                             .help("The url of your blog or website.")
+                                             .value_name("url")
+                                             .takes_value(true))
+                                        .arg(commands::format_arg()))
+}
+
+pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+    match matches.subcommand() {
+        ("view", Some(view_matches))           => view::handle(view_matches),
+        ("followers", Some(followers_matches)) => follows::handle(followers_matches, true),
+        ("following", Some(following_matches)) => follows::handle(following_matches, false),
+        ("edit", Some(edit_matches))           => edit::handle(edit_matches),
+        ("", None)                             => {
+            println!("No subcommand was used for user");
+            Ok(())
+        },
+        (_, _)                                 => unreachable!()
+    }
+}
+
+fn login_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("login")
+        .help("The login of the user.")
+}
+
+fn explain_error(error: GhError) -> GhError {
+    commands::explain_error(error, FORBIDDEN, NOT_FOUND)
+}
+
+const FORBIDDEN: &'static str = "403 Forbidden. Editing your profile needs the user scope. See https://developer.github.com/v3/users/";
+const NOT_FOUND: &'static str = "404 Not Found. Does the user exist? See https://developer.github.com/v3/users/";
+
+mod view {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, NL};
+use error::GhResult;
+use evidence::json_ops;
+use git_hub::users;
+use resources::users::User;
+use super::explain_error;
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let client = client_from_args(matches)?;
+        let user = match matches.value_of("login") {
+            Some(login) => users::get_user(login, &client),
+            None        => users::get_authed_user_profile(&client)
+        }.map_err(explain_error)?;
+        let output = &format_output(user, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    // GitHub sends an empty string for some fields cleared on the profile,
+    // null for others
+    fn non_empty(value: &Option<String>) -> Option<String> {
+        match *value {
+            Some(ref value) if !value.is_empty() => Some(value.clone()),
+            _                                    => None
+        }
+    }
+
+    pub fn format_output(user: User, is_json: bool) -> GhResult<String> {
+        if is_json {
+            return json_ops::to_pretty_json(&user, SERIALIZE_USER);
+        }
+        let mut fields: Vec<(&str, String)> = vec![];
+        for &(name, value) in &[("bio", &user.bio),
+                                ("company", &user.company),
+                                ("location", &user.location),
+                                ("email", &user.email),
+                                ("blog", &user.blog),
+                                ("twitter", &user.twitter_username)] {
+            if let Some(value) = non_empty(value) {
+                fields.push((name, value));
+            }
+        }
+        if let Some(hireable) = user.hireable {
+            fields.push(("hireable", if hireable { "yes" } else { "no" }.to_owned()));
+        }
+        fields.push(("url", user.html_url.clone()));
+        fields.push(("public repos", user.public_repos.to_string()));
+        if let Some(total) = user.total_private_repos {
+            fields.push(("private repos", total.to_string()));
+        }
+        fields.push(("followers", user.followers.to_string()));
+        fields.push(("following", user.following.to_string()));
+        if let Some(two_factor) = user.two_factor_authentication {
+            fields.push(("2fa", if two_factor { "enabled" } else { "disabled" }.to_owned()));
+        }
+        if let Some(ref plan) = user.plan {
+            fields.push(("plan", plan.name.clone()));
+        }
+        fields.push(("created", user.created_at.clone()));
+
+        let mut output = String::with_capacity(300);
+        match non_empty(&user.name) {
+            Some(name) => output.push_str(&format!("{} ({})", user.login, name)),
+            None       => output.push_str(&user.login)
+        }
+        if user.user_type != "User" {
+            output.push_str(&format!(" [{}]", user.user_type));
+        }
+        output.push_str(NL);
+        for (name, value) in fields {
+            output.push_str(&format!("{0: <14} {1}", name, value));
+            output.push_str(NL);
+        }
+        Ok(output)
+    }
+
+    const SERIALIZE_USER: &'static str = "Error serializing GitHub User JSON.";
+
+    #[cfg(test)]
+    mod tests {
+    use evidence::json_ops;
+    use super::format_output;
+
+        #[test]
+        fn test_format_output() -> () {
+            let body = "{\"login\":\"octocat\",\"id\":1,\"avatar_url\":\"\",\"gravatar_id\":\"\",\
+                        \"url\":\"https://api.github.com/users/octocat\",\"html_url\":\"https://github.com/octocat\",\
+                        \"type\":\"User\",\"site_admin\":false,\"name\":\"monalisa octocat\",\"company\":\"GitHub\",\
+                        \"blog\":\"\",\"location\":null,\"email\":\"octocat@github.com\",\"hireable\":false,\
+                        \"bio\":\"There once was...\",\"public_repos\":2,\"public_gists\":1,\"followers\":20,\
+                        \"following\":0,\"created_at\":\"2008-01-14T04:33:35Z\",\"updated_at\":\"2008-01-14T04:33:35Z\",\
+                        \"total_private_repos\":100,\"two_factor_authentication\":true,\
+                        \"plan\":{\"name\":\"Medium\",\"space\":400,\"private_repos\":20,\"collaborators\":0}}";
+            let output = format_output(json_ops::from_str(body, "").unwrap(), false).unwrap();
+            assert_eq!(output.lines().collect::<Vec<&str>>(),
+                       vec!["octocat (monalisa octocat)",
+                            "bio            There once was...",
+                            "company        GitHub",
+                            "email          octocat@github.com",
+                            "hireable       no",
+                            "url            https://github.com/octocat",
+                            "public repos   2",
+                            "private repos  100",
+                            "followers      20",
+                            "following      0",
+                            "2fa            enabled",
+                            "plan           Medium",
+                            "created        2008-01-14T04:33:35Z"]);
+        }
+    }
+}
+
+mod follows {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json, NL, pagination_from_args};
+use error::GhResult;
+use evidence::json_ops;
+use git_hub::users;
+use resources::users::UserSummary;
+use super::explain_error;
+
+    // Lists the followers of a user when `followers`, else those they follow
+    pub fn handle(matches: &ArgMatches, followers: bool) -> GhResult<()> {
+        let client = client_from_args(matches)?;
+        let pagination = pagination_from_args(matches);
+        let login = matches.value_of("login");
+        let users = if followers {
+            users::list_followers(login, &client, &pagination)
+        } else {
+            users::list_following(login, &client, &pagination)
+        }.map_err(explain_error)?;
+        let output = &format_output(users, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    fn format_output(users: Vec<UserSummary>, is_json: bool) -> GhResult<String> {
+        if is_json {
+            return json_ops::to_pretty_json(&users, SERIALIZE_USERS);
+        }
+        let mut output = String::with_capacity(100);
+        output.push_str(&format!("{0: <25} {1}", "login", "url"));
+        output.push_str(NL);
+        for user in users {
+            output.push_str(&format!("{0: <25} {1}", user.login, user.html_url));
+            output.push_str(NL);
+        }
+        Ok(output)
+    }
+
+    const SERIALIZE_USERS: &'static str = "Error serializing GitHub Users JSON.";
+}
+
+mod edit {
+use clap::ArgMatches;
+use commands::{client_from_args, is_json};
+use error::{GhError, GhResult};
+use evidence::json_ops;
+use git_hub::users;
+use git_hub::users::EditUser;
+use super::explain_error;
+use super::view::format_output;
+
+    pub fn handle(matches: &ArgMatches) -> GhResult<()> {
+        let edit = EditUser {
+            name: matches.value_of("name").map(|x| x.to_owned()),
+            bio: matches.value_of("bio").map(|x| x.to_owned()),
+            location: matches.value_of("location").map(|x| x.to_owned()),
+            blog: matches.value_of("blog").map(|x| x.to_owned())
+        };
+        if json_ops::to_json(&edit, SERIALIZE_EDIT_USER)? == "{}" {
+            return Err(GhError::Usage(NOTHING_TO_EDIT.to_owned()));
+        }
+        let user = users::edit_authed_user(&edit, &client_from_args(matches)?).map_err(explain_error)?;
+        let output = &format_output(user, is_json(matches))?;
+        println!("{}", output.trim());
+        Ok(())
+    }
+
+    const NOTHING_TO_EDIT: &'static str = "Nothing to edit. Pass at least one change, see gh user edit --help.";
+
+    const SERIALIZE_EDIT_USER: &'static str = "Error serializing GitHub User edit JSON.";
+}
@@ -0,0 +1,101 @@
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::io::prelude::*;
+use std::path::PathBuf;
+use std::process::{self, Command};
+
+// Opens the user's $VISUAL or $EDITOR (falling back to vi) on a new
+// temporary file seeded with `template`, and returns the saved contents once
+// the editor exits. The comment lines of the template, those starting with
+// `#`, are dropped again; any other line is kept, markdown headings included.
+pub fn edit(file_name: &str, template: &str) -> io::Result<String> {
+    let (path, mut file) = create_unique(file_name)?;
+    let written = file.write_all(template.as_bytes());
+    drop(file);
+    if let Err(e) = written {
+        let _ = fs::remove_file(&path);
+        return Err(e);
+    }
+
+    let editor = editor_command();
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let status = match Command::new(program).args(parts).arg(&path).status() {
+        Ok(status) => status,
+        Err(e)     => {
+            let _ = fs::remove_file(&path);
+            return Err(e);
+        }
+    };
+    if !status.success() {
+        let _ = fs::remove_file(&path);
+        let msg = format!("editor `{}` exited with {}", editor, status);
+        return Err(io::Error::new(io::ErrorKind::Other, msg));
+    }
+
+    let mut contents = String::new();
+    let read = File::open(&path).and_then(|mut f| f.read_to_string(&mut contents));
+    let _ = fs::remove_file(&path);
+    read?;
+    Ok(strip_comments(&contents, template))
+}
+
+// Creates a file in the temporary directory that no other gh run is using,
+// named after `file_name`, the process id and a counter. create_new refuses
+// a file that's already there, so two runs can never share one.
+fn create_unique(file_name: &str) -> io::Result<(PathBuf, File)> {
+    let mut attempt = 0;
+    loop {
+        let mut path = env::temp_dir();
+        path.push(format!("{}-{}-{}", file_name, process::id(), attempt));
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(file)                                                => return Ok((path, file)),
+            Err(ref e) if e.kind() == io::ErrorKind::AlreadyExists => attempt += 1,
+            Err(e)                                                  => return Err(e)
+        }
+    }
+}
+
+fn editor_command() -> String {
+    env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or("vi".to_owned())
+}
+
+// Drops the lines of `contents` that are one of the comment lines of
+// `template`.
+fn strip_comments(contents: &str, template: &str) -> String {
+    let comments: Vec<&str> = template.lines()
+                                      .filter(|line| line.starts_with('#'))
+                                      .collect();
+    let lines: Vec<&str> = contents.lines()
+                                   .filter(|line| !comments.contains(line))
+                                   .collect();
+    lines.join("\n").trim().to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+use super::strip_comments;
+
+    const TEMPLATE: &'static str = "\n# Enter a description above. This line is ignored.\n";
+
+    #[test]
+    fn test_strip_comments() -> () {
+        let contents = "Fixes the thing.\n\nReally.\n# Enter a description above. This line is ignored.\n";
+        assert_eq!(strip_comments(contents, TEMPLATE), "Fixes the thing.\n\nReally.");
+    }
+
+    #[test]
+    fn test_strip_comments_empty() -> () {
+        assert_eq!(strip_comments(TEMPLATE, TEMPLATE), "");
+    }
+
+    #[test]
+    fn test_strip_comments_keeps_headings() -> () {
+        let contents = "# Summary\nFixes the thing.\n\n## Testing\nRan it.\n\n\
+                        # Enter a description above. This line is ignored.\n";
+        assert_eq!(strip_comments(contents, TEMPLATE), "# Summary\nFixes the thing.\n\n## Testing\nRan it.");
+    }
+}
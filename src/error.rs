@@ -0,0 +1,203 @@
+use hyper;
+use hyper::status::StatusCode;
+
+use serde_json;
+
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+// Every failure gh can report. Each variant maps to a distinct process
+// exit code so scripts can tell, for example, a bad token from GitHub
+// being unreachable:
+//   1 - local I/O failure (reading a file, launching $EDITOR, ...)
+//   2 - invalid command line usage
+//   3 - missing or unreadable configuration
+//   4 - authentication or authorization failure (401 / 403)
+//   5 - any other unexpected HTTP status from GitHub (404, 422, 5xx, ...)
+//       GitHub's own error JSON is kept in GhError::Api, which exits 4 or 5
+//       by its status
+//   6 - network failure, GitHub could not be reached
+//   7 - JSON could not be encoded or decoded
+//   8 - GitHub's API rate limit was exceeded
+#[derive(Debug)]
+pub enum GhError {
+    Io(String, io::Error),
+    Usage(String),
+    ConfigMissing(PathBuf),
+    ConfigInvalid(String),
+    Auth(StatusCode, String),
+    Http(StatusCode, String),
+    Api(StatusCode, ApiError),
+    Network(String),
+    Json(String, serde_json::Error),
+    // Seconds until the limit resets, zero when GitHub didn't say
+    RateLimited(u64, String)
+}
+
+pub type GhResult<T> = Result<T, GhError>;
+
+impl GhError {
+    pub fn exit_code(&self) -> i32 {
+        match *self {
+            GhError::Io(_, _)          => 1,
+            GhError::Usage(_)          => 2,
+            GhError::ConfigMissing(_)  => 3,
+            GhError::ConfigInvalid(_)  => 3,
+            GhError::Auth(_, _)        => 4,
+            GhError::Http(_, _)        => 5,
+            GhError::Api(StatusCode::Unauthorized, _) => 4,
+            GhError::Api(StatusCode::Forbidden, _)    => 4,
+            GhError::Api(_, _)         => 5,
+            GhError::Network(_)        => 6,
+            GhError::Json(_, _)        => 7,
+            GhError::RateLimited(_, _) => 8
+        }
+    }
+}
+
+impl fmt::Display for GhError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            GhError::Io(ref msg, ref e)    => write!(f, "{} {}", msg, e),
+            GhError::Usage(ref msg)        => write!(f, "{}", msg),
+            GhError::ConfigMissing(ref p)  => write!(f, "no configuration found at {}. Run `gh config set` first.", p.display()),
+            GhError::ConfigInvalid(ref m)  => write!(f, "{}", m),
+            GhError::Auth(_, ref msg)      => write!(f, "{}", msg),
+            GhError::Http(_, ref msg)      => write!(f, "{}", msg),
+            GhError::Api(status, ref e)    => write!(f, "{}: {}", status, e),
+            GhError::Network(ref msg)      => write!(f, "Unable to reach GitHub. {}", msg),
+            GhError::Json(ref msg, ref e)  => write!(f, "{} {}", msg, e),
+            GhError::RateLimited(_, ref m) => write!(f, "{}", m)
+        }
+    }
+}
+
+// The error JSON GitHub returns with a 4xx response, e.g.
+// {"message":"Validation Failed","errors":[{"resource":"Issue","field":"title","code":"missing_field"}],
+//  "documentation_url":"https://developer.github.com/v3/issues/#create-an-issue"}
+// See https://developer.github.com/v3/#client-errors
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ApiError {
+    pub message: String,
+    #[serde(default)]
+    pub documentation_url: Option<String>,
+    #[serde(default)]
+    pub errors: Vec<ApiErrorDetail>
+}
+
+// A single entry of `errors`. Most endpoints describe the field at fault,
+// a few only send a message string.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ApiErrorDetail {
+    Message(String),
+    Field {
+        #[serde(default)]
+        resource: Option<String>,
+        #[serde(default)]
+        field: Option<String>,
+        #[serde(default)]
+        code: Option<String>,
+        #[serde(default)]
+        message: Option<String>
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        if !self.errors.is_empty() {
+            let details: Vec<String> = self.errors.iter().map(|e| e.to_string()).collect();
+            write!(f, " ({})", details.join("; "))?;
+        }
+        match self.documentation_url {
+            Some(ref url) => write!(f, ". See {}", url),
+            None          => Ok(())
+        }
+    }
+}
+
+impl fmt::Display for ApiErrorDetail {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ApiErrorDetail::Message(ref msg) => write!(f, "{}", msg),
+            ApiErrorDetail::Field { message: Some(ref msg), .. } => write!(f, "{}", msg),
+            ApiErrorDetail::Field { ref resource, ref field, ref code, .. } => {
+                let field = match (resource.as_ref(), field.as_ref()) {
+                    (Some(resource), Some(field)) => format!("{}.{}", resource, field),
+                    (Some(name), None)            => name.to_owned(),
+                    (None, Some(name))            => name.to_owned(),
+                    (None, None)                  => "request".to_owned()
+                };
+                write!(f, "{} {}", field, code.as_ref().map(|c| c.as_str()).unwrap_or("invalid"))
+            }
+        }
+    }
+}
+
+impl From<hyper::Error> for GhError {
+    fn from(e: hyper::Error) -> GhError {
+        GhError::Network(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+use hyper::status::StatusCode;
+use serde_json;
+use std::io;
+use std::path::PathBuf;
+use super::{ApiError, GhError};
+
+    #[test]
+    fn test_exit_codes() -> () {
+        let api_error = || ApiError { message: "".to_owned(), documentation_url: None, errors: vec![] };
+        let errors = vec![
+            (GhError::Io("".to_owned(), io::Error::new(io::ErrorKind::Other, "")), 1),
+            (GhError::Usage("".to_owned()), 2),
+            (GhError::ConfigMissing(PathBuf::from("/tmp")), 3),
+            (GhError::ConfigInvalid("".to_owned()), 3),
+            (GhError::Auth(StatusCode::Unauthorized, "".to_owned()), 4),
+            (GhError::Http(StatusCode::InternalServerError, "".to_owned()), 5),
+            (GhError::Api(StatusCode::Unauthorized, api_error()), 4),
+            (GhError::Api(StatusCode::UnprocessableEntity, api_error()), 5),
+            (GhError::Network("".to_owned()), 6),
+            (GhError::Json("".to_owned(), serde_json::from_str::<u32>("").unwrap_err()), 7),
+            (GhError::RateLimited(0, "".to_owned()), 8)
+        ];
+        for (error, code) in errors {
+            assert_eq!(error.exit_code(), code, "{:?}", error);
+        }
+    }
+
+    #[test]
+    fn test_api_error_exit_codes() -> () {
+        let error = |status| {
+            GhError::Api(status, ApiError { message: "".to_owned(), documentation_url: None, errors: vec![] })
+        };
+        assert_eq!(error(StatusCode::Forbidden).exit_code(), 4);
+        assert_eq!(error(StatusCode::NotFound).exit_code(), 5);
+    }
+
+    #[test]
+    fn test_api_error_display() -> () {
+        let body = "{\"message\":\"Validation Failed\",\
+                    \"errors\":[{\"resource\":\"Issue\",\"field\":\"title\",\"code\":\"missing_field\"},\
+                    {\"resource\":\"PullRequest\",\"code\":\"custom\",\"message\":\"No commits between master and topic\"},\
+                    \"Label does not exist\"],\
+                    \"documentation_url\":\"https://developer.github.com/v3/issues/#create-an-issue\"}";
+        let api_error: ApiError = serde_json::from_str(body).unwrap();
+        assert_eq!(GhError::Api(StatusCode::UnprocessableEntity, api_error).to_string(),
+                   "422 Unprocessable Entity: Validation Failed (Issue.title missing_field; \
+                    No commits between master and topic; Label does not exist). \
+                    See https://developer.github.com/v3/issues/#create-an-issue");
+    }
+
+    #[test]
+    fn test_display_is_one_line() -> () {
+        let error = GhError::ConfigMissing(PathBuf::from("/home/octocat/.config/gh/credentials"));
+        assert_eq!(error.to_string(),
+                   "no configuration found at /home/octocat/.config/gh/credentials. Run `gh config set` first.");
+    }
+}
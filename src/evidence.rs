@@ -1,35 +1,61 @@
 // A few commonly used wrapper functions around Serde JSON
 // to encapsulate common behaviour
 pub mod json_ops {
+use error::{GhError, GhResult};
 use serde_json;
 use serde::de::DeserializeOwned;
 use serde::ser::Serialize;
+#[cfg(test)]
+use std::iter;
 
-    pub fn from_str_or_die<T: DeserializeOwned>
-        (s: &str, error_msg: &str) -> T {
-        let t: T = match serde_json::from_str(s) {
-            Ok(t)  => t,
-            Err(x) => {
-                let panic_msg = format!("{} {}", error_msg, x);
-                panic!(panic_msg)
-            },
-        };
-        t
+    pub fn from_str<T: DeserializeOwned>
+        (s: &str, error_msg: &str) -> GhResult<T> {
+        serde_json::from_str(s).map_err(|x| GhError::Json(error_msg.to_owned(), x))
     }
 
-    pub fn to_pretty_json_or_die<T: Serialize>
-        (t: &T, error_msg: &str) -> String {
-        match serde_json::to_string_pretty(t) {
-            Ok(json_str) => json_str,
-            Err(x)       => {
-                let panic_msg = format!("{} {}", error_msg, x);
-                panic!(panic_msg)
-            },
+    pub fn to_json<T: Serialize>
+        (t: &T, error_msg: &str) -> GhResult<String> {
+        serde_json::to_string(t).map_err(|x| GhError::Json(error_msg.to_owned(), x))
+    }
+
+    pub fn to_pretty_json<T: Serialize>
+        (t: &T, error_msg: &str) -> GhResult<String> {
+        serde_json::to_string_pretty(t).map_err(|x| GhError::Json(error_msg.to_owned(), x))
+    }
+
+    // Varies a JSON object the way GitHub's responses vary, for property
+    // tests of the models decoding them. Following `choices` in turn, each
+    // of `nullable` is left out, set to null or kept, and each of `omittable`
+    // left out or kept. A field no model knows is added with `text` as its
+    // value.
+    #[cfg(test)]
+    pub fn vary_fields(json: &str, nullable: &[&str], omittable: &[&str], choices: &[u8], text: &str) -> String {
+        let mut value: serde_json::Value = serde_json::from_str(json).unwrap();
+        {
+            let object = value.as_object_mut().unwrap();
+            // Fields past the end of `choices` are kept
+            let mut choices = choices.iter().map(|x| Some(*x)).chain(iter::repeat(None));
+            for field in nullable {
+                match choices.next().unwrap().map(|x| x % 3) {
+                    Some(0) => { object.remove(*field); },
+                    Some(1) => { object.insert(field.to_string(), serde_json::Value::Null); },
+                    _       => ()
+                }
+            }
+            for field in omittable {
+                if choices.next().unwrap().map(|x| x % 2) == Some(0) {
+                    object.remove(*field);
+                }
+            }
+            object.insert("unknown_to_gh".to_owned(), serde_json::Value::String(text.to_owned()));
         }
+        serde_json::to_string(&value).unwrap()
     }
 
     #[cfg(test)]
     mod tests {
+    use error::GhError;
+
         #[derive(Debug, Deserialize, PartialEq, Serialize)]
         struct Test {
             id: u32,
@@ -43,27 +69,40 @@ use serde::ser::Serialize;
         }
 
         #[test]
-        fn test_from_str_or_die_deserialize() {
+        fn test_from_str_deserialize() {
             let json_str = "{\"id\":1,\"name\":\"Foo bar\"}";
-            let result: Test = super::from_str_or_die(json_str,
-                                                     "Doesn't matter.");
+            let result: Test = super::from_str(json_str, "Doesn't matter.").unwrap();
             assert_eq!(result, build_test_struct());
         }
 
         #[test]
-        #[should_panic]
-        fn test_from_str_or_die_die() -> () {
+        fn test_from_str_error() -> () {
             let json_str = "This isn't valid JSON!";
-            let _: Test = super::from_str_or_die(json_str,
-                                                 "Doesn't matter.");
+            let result: Result<Test, GhError> = super::from_str(json_str, "Doesn't matter.");
+            match result {
+                Err(GhError::Json(msg, _)) => assert_eq!(msg, "Doesn't matter."),
+                _                          => panic!("expected a JSON error")
+            }
+        }
+
+        #[test]
+        fn test_to_json_serialize() -> () {
+            let result = super::to_json(&build_test_struct(), "Doesn't matter.").unwrap();
+            assert_eq!(result, "{\"id\":1,\"name\":\"Foo bar\"}");
         }
 
         #[test]
-        fn test_to_pretty_json_or_die_serialize() -> () {
-            let result = super::to_pretty_json_or_die(&build_test_struct(),
-                                                     "Doesn't matter.");
+        fn test_to_pretty_json_serialize() -> () {
+            let result = super::to_pretty_json(&build_test_struct(), "Doesn't matter.").unwrap();
             let json_str = "{\n  \"id\": 1,\n  \"name\": \"Foo bar\"\n}";
             assert_eq!(result, json_str);
         }
+
+        #[test]
+        fn test_vary_fields() -> () {
+            let json_str = "{\"a\":1,\"b\":2,\"c\":3,\"d\":4}";
+            assert_eq!(super::vary_fields(json_str, &["a", "b"], &["c", "d"], &[0, 1, 0], "x"),
+                       "{\"b\":null,\"d\":4,\"unknown_to_gh\":\"x\"}");
+        }
     }
 }
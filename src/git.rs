@@ -0,0 +1,295 @@
+// Reads the git checkout gh is run from, so repository scoped commands can
+// default to its GitHub repository and current branch. Only the files under
+// .git are read for that, git itself is only run to change the checkout.
+use error::{GhError, GhResult};
+
+use std::env;
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// Remotes are only taken for GitHub repositories when they point at
+// github.com or `host`, the host of the configured API root, see web_host.
+
+// The GitHub repository of the checkout containing the working directory,
+// taken from its `upstream` remote when there is one, `origin` otherwise.
+pub fn current_repo(host: &str) -> Option<(String, String)> {
+    ["upstream", "origin"].iter()
+                          .filter_map(|name| remote_repo(name, host))
+                          .next()
+}
+
+// The GitHub repository the current checkout's remote `name` points at.
+pub fn remote_repo(name: &str, host: &str) -> Option<(String, String)> {
+    current_remotes().into_iter()
+                     .find(|remote| remote.0 == name)
+                     .and_then(|remote| parse_remote_url(&remote.1, host))
+}
+
+// The name of the current checkout's remote for owner/repo, if it has one.
+pub fn remote_name(owner: &str, repo: &str, host: &str) -> Option<String> {
+    current_remotes().into_iter()
+                     .find(|remote| match parse_remote_url(&remote.1, host) {
+                         Some((ref o, ref r)) => o.eq_ignore_ascii_case(owner) && r.eq_ignore_ascii_case(repo),
+                         None                 => false
+                     })
+                     .map(|remote| remote.0)
+}
+
+// The host git remotes point at for a GitHub API root: github.com for
+// https://api.github.com, ghe.example.com for https://ghe.example.com/api/v3.
+pub fn web_host(api_url: &str) -> String {
+    let rest = match api_url.find("://") {
+        Some(index) => &api_url[index + 3..],
+        None        => api_url
+    };
+    let host = rest.split(|c| c == '/' || c == ':').next().unwrap_or("").to_lowercase();
+    if host.starts_with("api.") { host["api.".len()..].to_owned() } else { host }
+}
+
+// Runs git with `args` in the working directory, its output going straight
+// to the terminal.
+pub fn run(args: &[&str]) -> GhResult<()> {
+    let command = format!("git {}", args.join(" "));
+    match Command::new("git").args(args).status() {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => {
+            let msg = format!("exited with {}", status);
+            Err(GhError::Io(format!("`{}`", command), io::Error::new(io::ErrorKind::Other, msg)))
+        },
+        Err(e) => Err(GhError::Io(format!("could not run `{}`", command), e))
+    }
+}
+
+// Whether `ancestor` is reachable from `commit`, i.e. `commit` can be
+// fast-forwarded to from `ancestor`.
+pub fn is_ancestor(ancestor: &str, commit: &str) -> GhResult<bool> {
+    let args = ["merge-base", "--is-ancestor", ancestor, commit];
+    let command = format!("git {}", args.join(" "));
+    match Command::new("git").args(&args).status() {
+        Ok(status) => match status.code() {
+            Some(0) => Ok(true),
+            Some(1) => Ok(false),
+            _       => {
+                let msg = format!("exited with {}", status);
+                Err(GhError::Io(format!("`{}`", command), io::Error::new(io::ErrorKind::Other, msg)))
+            }
+        },
+        Err(e) => Err(GhError::Io(format!("could not run `{}`", command), e))
+    }
+}
+
+// The branch checked out in the checkout containing the working directory,
+// or None when HEAD is detached.
+pub fn current_branch() -> Option<String> {
+    find_git_dir().and_then(|git_dir| read_to_string(&git_dir.join("HEAD")))
+                  .and_then(|head| parse_head(&head))
+}
+
+// Walks up from the working directory to the nearest .git, which is a
+// directory in a plain checkout, or a file pointing at one in a worktree or
+// submodule.
+fn find_git_dir() -> Option<PathBuf> {
+    let mut dir = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(_)  => return None,
+    };
+    loop {
+        let dot_git = dir.join(".git");
+        if dot_git.is_dir() {
+            return Some(dot_git);
+        }
+        if dot_git.is_file() {
+            return read_to_string(&dot_git).and_then(|contents| parse_gitdir_file(&dir, &contents));
+        }
+        if !dir.pop() {
+            return None;
+        }
+    }
+}
+
+fn current_remotes() -> Vec<(String, String)> {
+    match find_git_dir().and_then(|git_dir| read_to_string(&config_path(&git_dir))) {
+        Some(config) => parse_remotes(&config),
+        None         => vec![]
+    }
+}
+
+// Worktrees keep their config in the main checkout's git directory, named
+// by a `commondir` file.
+fn config_path(git_dir: &Path) -> PathBuf {
+    match read_to_string(&git_dir.join("commondir")) {
+        Some(common_dir) => git_dir.join(common_dir.trim()).join("config"),
+        None             => git_dir.join("config")
+    }
+}
+
+fn read_to_string(path: &Path) -> Option<String> {
+    let mut contents = String::new();
+    match File::open(path).and_then(|mut f| f.read_to_string(&mut contents)) {
+        Ok(_)  => Some(contents),
+        Err(_) => None
+    }
+}
+
+// Parses `gitdir: ../.git/worktrees/topic`, relative to the checkout.
+fn parse_gitdir_file(dir: &Path, contents: &str) -> Option<PathBuf> {
+    contents.lines()
+            .filter_map(|line| line.trim().splitn(2, "gitdir:").nth(1))
+            .map(|path| dir.join(path.trim()))
+            .next()
+}
+
+// Parses `ref: refs/heads/master` out of .git/HEAD.
+fn parse_head(head: &str) -> Option<String> {
+    head.trim()
+        .splitn(2, "ref: refs/heads/")
+        .nth(1)
+        .map(|branch| branch.to_owned())
+}
+
+// Parses the name and url of every `[remote "name"]` section of a git config.
+fn parse_remotes(config: &str) -> Vec<(String, String)> {
+    let mut remotes = vec![];
+    let mut remote: Option<String> = None;
+    for line in config.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            remote = parse_remote_section(line);
+            continue;
+        }
+        let name = match remote {
+            Some(ref name) => name,
+            None           => continue,
+        };
+        let mut parts = line.splitn(2, '=');
+        match (parts.next(), parts.next()) {
+            (Some(key), Some(value)) if key.trim().eq_ignore_ascii_case("url") => {
+                remotes.push((name.to_owned(), value.trim().trim_matches('"').to_owned()));
+            },
+            _ => ()
+        }
+    }
+    remotes
+}
+
+fn parse_remote_section(line: &str) -> Option<String> {
+    let section = line.trim_start_matches('[').trim_end_matches(']').trim();
+    if !section.starts_with("remote ") {
+        return None;
+    }
+    Some(section["remote ".len()..].trim().trim_matches('"').to_owned())
+}
+
+// Parses the owner and repository out of a remote url on github.com or
+// `host`, in any of the forms GitHub hands out:
+//   git@github.com:octocat/Hello-World.git
+//   ssh://git@github.com/octocat/Hello-World.git
+//   https://github.com/octocat/Hello-World.git
+// Remotes on any other host, GitLab or Bitbucket say, are None.
+pub fn parse_remote_url(url: &str, host: &str) -> Option<(String, String)> {
+    let (authority, path) = if let Some(index) = url.find("://") {
+        let rest = &url[index + 3..];
+        match rest.find('/') {
+            Some(slash) => (&rest[..slash], &rest[slash + 1..]),
+            None        => return None,
+        }
+    } else {
+        match url.find(':') {
+            Some(colon) => (&url[..colon], &url[colon + 1..]),
+            None        => return None,
+        }
+    };
+    // drop the user@ and :port around the host
+    let remote_host = authority.rsplit('@').next().unwrap_or("");
+    let remote_host = remote_host.split(':').next().unwrap_or("");
+    if !remote_host.eq_ignore_ascii_case(GITHUB_HOST) && !remote_host.eq_ignore_ascii_case(host) {
+        return None;
+    }
+    let path = path.trim_matches('/');
+    let path = if path.ends_with(".git") { &path[..path.len() - 4] } else { path };
+    let parts: Vec<&str> = path.split('/').collect();
+    match parts.as_slice() {
+        [owner, repo] if !owner.is_empty() && !repo.is_empty() => Some((owner.to_string(), repo.to_string())),
+        _ => None
+    }
+}
+
+const GITHUB_HOST: &'static str = "github.com";
+
+#[cfg(test)]
+mod tests {
+use std::path::{Path, PathBuf};
+use super::{parse_gitdir_file, parse_head, parse_remote_url, parse_remotes, web_host};
+
+    fn repo(owner: &str, name: &str) -> Option<(String, String)> {
+        Some((owner.to_owned(), name.to_owned()))
+    }
+
+    #[test]
+    fn test_parse_remote_url() -> () {
+        let host = "github.com";
+        assert_eq!(parse_remote_url("git@github.com:octocat/Hello-World.git", host), repo("octocat", "Hello-World"));
+        assert_eq!(parse_remote_url("ssh://git@github.com/octocat/Hello-World.git", host), repo("octocat", "Hello-World"));
+        assert_eq!(parse_remote_url("https://github.com/octocat/Hello-World.git", host), repo("octocat", "Hello-World"));
+        assert_eq!(parse_remote_url("https://github.com/octocat/Hello-World/", host), repo("octocat", "Hello-World"));
+    }
+
+    #[test]
+    fn test_parse_remote_url_enterprise() -> () {
+        let host = "github.example.com";
+        assert_eq!(parse_remote_url("ssh://git@github.example.com:2222/octocat/Hello-World", host),
+                   repo("octocat", "Hello-World"));
+        assert_eq!(parse_remote_url("git@github.com:octocat/Hello-World.git", host), repo("octocat", "Hello-World"));
+        assert_eq!(parse_remote_url("ssh://git@github.example.com:2222/octocat/Hello-World", "github.com"), None);
+    }
+
+    #[test]
+    fn test_parse_remote_url_other_host() -> () {
+        assert_eq!(parse_remote_url("git@gitlab.com:octocat/Hello-World.git", "github.com"), None);
+        assert_eq!(parse_remote_url("https://bitbucket.org/octocat/Hello-World.git", "github.com"), None);
+    }
+
+    #[test]
+    fn test_parse_remote_url_not_a_repository() -> () {
+        assert_eq!(parse_remote_url("/srv/git/Hello-World.git", "github.com"), None);
+        assert_eq!(parse_remote_url("https://github.com/octocat", "github.com"), None);
+    }
+
+    #[test]
+    fn test_web_host() -> () {
+        assert_eq!(web_host("https://api.github.com"), "github.com");
+        assert_eq!(web_host("https://ghe.example.com/api/v3"), "ghe.example.com");
+        assert_eq!(web_host("http://localhost:8080/api/v3"), "localhost");
+    }
+
+    #[test]
+    fn test_parse_remotes() -> () {
+        let config = "[core]\n\
+                      \trepositoryformatversion = 0\n\
+                      [remote \"origin\"]\n\
+                      \turl = git@github.com:hubot/Hello-World.git\n\
+                      \tfetch = +refs/heads/*:refs/remotes/origin/*\n\
+                      [branch \"master\"]\n\
+                      \tremote = origin\n\
+                      [remote \"upstream\"]\n\
+                      \turl = https://github.com/octocat/Hello-World.git\n";
+        assert_eq!(parse_remotes(config),
+                   vec![("origin".to_owned(), "git@github.com:hubot/Hello-World.git".to_owned()),
+                        ("upstream".to_owned(), "https://github.com/octocat/Hello-World.git".to_owned())]);
+    }
+
+    #[test]
+    fn test_parse_head() -> () {
+        assert_eq!(parse_head("ref: refs/heads/feature/login\n"), Some("feature/login".to_owned()));
+        assert_eq!(parse_head("6dcb09b5b57875f334f61aebed695e2e4193db5e\n"), None);
+    }
+
+    #[test]
+    fn test_parse_gitdir_file() -> () {
+        assert_eq!(parse_gitdir_file(Path::new("/src/topic"), "gitdir: /src/main/.git/worktrees/topic\n"),
+                   Some(PathBuf::from("/src/main/.git/worktrees/topic")));
+    }
+}
@@ -0,0 +1,69 @@
+use error::GhResult;
+
+use git_hub::Client;
+
+// The check runs reported against a commit.
+// See https://developer.github.com/v3/checks/runs/
+#[derive(Deserialize, Serialize)]
+pub struct CheckRuns {
+    pub total_count: u32,
+    pub check_runs: Vec<CheckRun>
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct CheckRun {
+    pub id: u64,
+    pub name: String,
+    // queued, in_progress or completed
+    pub status: String,
+    // success, failure, neutral, cancelled, skipped, timed_out or
+    // action_required once completed, null before
+    pub conclusion: Option<String>,
+    pub html_url: Option<String>
+}
+
+// The first hundred check runs of `git_ref`, a commit sha, branch or tag.
+pub fn list_check_runs(owner: &str, repo: &str, git_ref: &str, client: &Client) -> GhResult<CheckRuns> {
+    client.send(requests::ListCheckRuns {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        git_ref: git_ref.to_owned()
+    })
+}
+
+mod requests {
+use config::Config;
+use error::GhResult;
+use git_hub::{parse_url, GitHubRequest};
+use git_hub::checks::CheckRuns;
+use hyper::Url;
+
+    // Lists the check runs of a commit
+    // GET /repos/:owner/:repo/commits/:ref/check-runs
+    // See https://developer.github.com/v3/checks/runs/#list-check-runs-for-a-specific-ref
+    // for more information
+    pub struct ListCheckRuns {
+        pub owner: String,
+        pub repo: String,
+        pub git_ref: String
+    }
+
+    impl GitHubRequest for ListCheckRuns {
+        type Response = CheckRuns;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = String::with_capacity(80);
+            endpoint.push_str(config.api_url());
+            endpoint.push_str("/repos/");
+            endpoint.push_str(&self.owner);
+            endpoint.push_str("/");
+            endpoint.push_str(&self.repo);
+            endpoint.push_str("/commits/");
+            endpoint.push_str(&self.git_ref);
+            endpoint.push_str("/check-runs");
+            let mut url = parse_url(&endpoint)?;
+            url.query_pairs_mut().append_pair("per_page", "100");
+            Ok(url)
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use config::Config;
+use error::{GhError, GhResult};
+
+use git_hub::{add_headers, connector, decode_response, set_media_type, GitHubRequest, GitHubResponse};
+use git_hub::rate_limit::with_rate_limit;
+
+use hyper;
+use hyper::Url;
+use hyper::client::pool::Pool;
+use hyper::header::{ContentType, Headers};
+use hyper::method::Method;
+
+use std::io::Read;
+
+// Executes GitHub requests for one configuration. The TLS connector is built
+// once and kept alive connections are pooled, so paging through a list or
+// making several calls in one command doesn't renegotiate TLS each time.
+// `wait_on_rate_limit` is set from --wait-for-rate-limit for the one
+// invocation: sleep until a rate limit resets and retry, rather than failing.
+pub struct Client {
+    http: hyper::Client,
+    config: Config,
+    wait_on_rate_limit: bool
+}
+
+impl Client {
+    pub fn new(config: &Config, wait_on_rate_limit: bool) -> GhResult<Client> {
+        Ok(Client {
+            http: hyper::Client::with_connector(Pool::with_connector(Default::default(), connector()?)),
+            config: config.clone(),
+            wait_on_rate_limit: wait_on_rate_limit
+        })
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    // Executes `request` and decodes its response, see decode_response.
+    pub fn send<R: GitHubRequest>(&self, request: R) -> GhResult<R::Response> {
+        decode_response(&request, &self.execute(&request)?)
+    }
+
+    // Executes `request`, waiting out rate limits if the client was asked to.
+    pub fn execute<R: GitHubRequest>(&self, request: &R) -> GhResult<GitHubResponse> {
+        let url = request.url(&self.config)?;
+        with_rate_limit(self.wait_on_rate_limit, || {
+            self.send_once(request.method(), &url, request.body(), request.media_type())
+        })
+    }
+
+    // Executes a request against an absolute URL, such as the next page of a
+    // list. A body is sent as JSON.
+    pub fn execute_url(&self,
+                       method: Method,
+                       url: &Url,
+                       body: Option<&str>) -> GhResult<GitHubResponse> {
+        with_rate_limit(self.wait_on_rate_limit, || self.send_once(method.clone(), url, body, None))
+    }
+
+    fn send_once(&self,
+                 method: Method,
+                 url: &Url,
+                 body: Option<&str>,
+                 media_type: Option<&str>) -> GhResult<GitHubResponse> {
+        let mut headers = Headers::new();
+        add_headers(&mut headers, &self.config);
+        if let Some(media_type) = media_type {
+            set_media_type(&mut headers, media_type);
+        }
+        let builder = self.http.request(method, url.clone());
+        let builder = match body {
+            Some(body) => {
+                headers.set(ContentType::json());
+                builder.headers(headers).body(body)
+            },
+            None => builder.headers(headers),
+        };
+        let mut response = builder.send()?;
+        let mut body = vec![];
+        response.read_to_end(&mut body)
+                .map_err(|e| GhError::Network(format!("Could not read GitHub response. {}", e)))?;
+        let s: String = String::from_utf8_lossy(&body).into_owned();
+        Ok(GitHubResponse {
+            status: response.status,
+            headers: response.headers.clone(),
+            body: if s.trim().is_empty() { None } else { Some(s) },
+        })
+    }
+}
@@ -0,0 +1,143 @@
+// GitHub's GraphQL API, used for the few things the REST API can't do, such
+// as moving a Pull Request in and out of draft.
+// See https://developer.github.com/v4/
+use config::Config;
+use error::{GhError, GhResult};
+use evidence::json_ops;
+
+use git_hub::Client;
+
+use hyper::status::StatusCode;
+
+use serde::Serialize;
+use serde_json::Value;
+
+// The body of a GraphQL request
+#[derive(Serialize)]
+struct GraphQlQuery<'a, V: Serialize> {
+    query: &'a str,
+    variables: V
+}
+
+// GraphQL answers 200 OK even when a query fails, naming what went wrong in
+// `errors` instead
+#[derive(Deserialize)]
+struct GraphQlResponse {
+    data: Option<Value>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>
+}
+
+#[derive(Deserialize)]
+struct GraphQlError {
+    message: String
+}
+
+// Runs `query` with `variables`, returning its `data`.
+pub fn query<V: Serialize>(query: &str, variables: V, client: &Client) -> GhResult<Value> {
+    let body = GraphQlQuery {
+        query: query,
+        variables: variables
+    };
+    client.send(requests::GraphQlRequest {
+        body: json_ops::to_json(&body, SERIALIZE_QUERY)?
+    })
+}
+
+// GitHub Enterprise Server serves GraphQL at /api/graphql, beside the REST
+// API at /api/v3, while github.com serves it at api.github.com/graphql.
+pub fn graphql_url(config: &Config) -> String {
+    let api_url = config.api_url();
+    if api_url.ends_with("/api/v3") {
+        format!("{}/api/graphql", &api_url[..api_url.len() - "/api/v3".len()])
+    } else {
+        format!("{}/graphql", api_url)
+    }
+}
+
+fn decode_data(status: StatusCode, body: &str) -> GhResult<Value> {
+    let response: GraphQlResponse = json_ops::from_str(body, DECODE_RESPONSE)?;
+    if response.errors.is_empty() {
+        Ok(response.data.unwrap_or(Value::Null))
+    } else {
+        let messages: Vec<&str> = response.errors.iter().map(|x| x.message.as_str()).collect();
+        Err(GhError::Http(status, format!("{} {}", QUERY_FAILED, messages.join("; "))))
+    }
+}
+
+const SERIALIZE_QUERY: &'static str = "Error serializing GitHub GraphQL query JSON.";
+const DECODE_RESPONSE: &'static str = "Error deserializing GitHub GraphQL response JSON.";
+const QUERY_FAILED: &'static str = "GitHub's GraphQL API refused the request.";
+
+mod requests {
+use config::Config;
+use error::GhResult;
+use git_hub::{parse_url, GitHubRequest, GitHubResponse};
+use git_hub::graphql::{decode_data, graphql_url};
+use hyper::Url;
+use hyper::method::Method;
+use serde_json::Value;
+
+    // Runs a GraphQL query or mutation
+    // POST /graphql
+    // See https://developer.github.com/v4/guides/forming-calls/
+    // for more information
+    pub struct GraphQlRequest {
+        pub body: String
+    }
+
+    impl GitHubRequest for GraphQlRequest {
+        type Response = Value;
+
+        fn method(&self) -> Method {
+            Method::Post
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            parse_url(&graphql_url(config))
+        }
+
+        fn body(&self) -> Option<&str> {
+            Some(&self.body)
+        }
+
+        fn decode(&self, response: &GitHubResponse) -> GhResult<Value> {
+            match response.body {
+                Some(ref body) => decode_data(response.status, body),
+                None           => Ok(Value::Null)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+use config::Config;
+use hyper::status::StatusCode;
+use super::{decode_data, graphql_url};
+
+    fn build_test_config(api_url: Option<&str>) -> Config {
+        Config {
+            username: "octocat".to_owned(),
+            access_token: "abcdefg1234567".to_owned(),
+            api_url: api_url.map(|x| x.to_owned()),
+            credential_helper: None
+        }
+    }
+
+    #[test]
+    fn test_graphql_url() -> () {
+        assert_eq!(graphql_url(&build_test_config(None)), "https://api.github.com/graphql");
+        assert_eq!(graphql_url(&build_test_config(Some("https://ghe.example.com/api/v3"))),
+                   "https://ghe.example.com/api/graphql");
+    }
+
+    #[test]
+    fn test_decode_data_errors() -> () {
+        let body = "{\"data\":null,\"errors\":[{\"type\":\"NOT_FOUND\",\"message\":\"Could not resolve to a node with the global id of 'x'\"}]}";
+        let error = decode_data(StatusCode::Ok, body).unwrap_err();
+        assert_eq!(error.to_string(),
+                   "GitHub's GraphQL API refused the request. Could not resolve to a node with the global id of 'x'");
+        assert_eq!(error.exit_code(), 5);
+    }
+}
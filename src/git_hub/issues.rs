@@ -0,0 +1,669 @@
+use error::GhResult;
+
+use evidence::json_ops;
+use git_hub::Client;
+use git_hub::pagination;
+use git_hub::pagination::Pagination;
+
+use hyper::method::Method;
+
+use serde_json::Value;
+
+// GitHub Issue. See https://developer.github.com/v3/issues/
+// Pull Requests are Issues too, and carry a `pull_request` link.
+#[derive(Deserialize, Serialize)]
+pub struct Issue {
+    pub id: u64,
+    pub number: u32,
+    pub state: String,
+    pub title: String,
+    pub body: Option<String>,
+    // null once the author's account has been deleted
+    pub user: Option<IssueUser>,
+    #[serde(default)]
+    pub labels: Vec<IssueLabel>,
+    #[serde(default)]
+    pub assignees: Vec<IssueUser>,
+    pub milestone: Option<IssueMilestone>,
+    pub comments: u32,
+    pub url: String,
+    pub html_url: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub closed_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pull_request: Option<IssuePullRequest>
+}
+
+// The GitHub User who opened, is assigned to, or commented on an Issue
+#[derive(Deserialize, Serialize)]
+pub struct IssueUser {
+    pub login: String,
+    pub id: u64
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct IssueLabel {
+    pub name: String,
+    pub color: String,
+    #[serde(default)]
+    pub description: Option<String>
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct IssueMilestone {
+    pub number: u32,
+    pub title: String,
+    pub state: String
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct IssuePullRequest {
+    pub url: String,
+    pub html_url: String
+}
+
+// A comment on an Issue.
+// See https://developer.github.com/v3/issues/comments/
+#[derive(Deserialize, Serialize)]
+pub struct IssueComment {
+    pub id: u64,
+    pub body: String,
+    pub user: Option<IssueUser>,
+    pub html_url: String,
+    pub created_at: String,
+    pub updated_at: String
+}
+
+// The body of a request to open a new Issue.
+// See https://developer.github.com/v3/issues/#create-an-issue
+#[derive(Deserialize, Serialize)]
+pub struct NewIssue {
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub assignees: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<u32>
+}
+
+// The body of a request to edit an Issue. Only the fields given are changed.
+// See https://developer.github.com/v3/issues/#edit-an-issue
+#[derive(Default, Deserialize, Serialize)]
+pub struct EditIssue {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    // completed or not_planned, when closing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_reason: Option<String>
+}
+
+#[derive(Deserialize, Serialize)]
+struct NewIssueComment {
+    body: String
+}
+
+#[derive(Deserialize, Serialize)]
+struct IssueLabels {
+    labels: Vec<String>
+}
+
+#[derive(Deserialize, Serialize)]
+struct IssueAssignees {
+    assignees: Vec<String>
+}
+
+// How to filter a repository's Issues.
+//   state     - open, closed or all
+//   labels    - only Issues carrying every one of these labels
+//   assignee  - a login, `none` or `*`
+//   milestone - a milestone number, `none` or `*`
+//   creator   - the login that opened the Issue
+//   since     - only Issues updated at or after this ISO 8601 timestamp
+#[derive(Clone, Default)]
+pub struct IssueFilters {
+    pub state: Option<String>,
+    pub labels: Vec<String>,
+    pub assignee: Option<String>,
+    pub milestone: Option<String>,
+    pub creator: Option<String>,
+    pub since: Option<String>
+}
+
+pub fn list_issues(owner: &str,
+                   repo: &str,
+                   filters: &IssueFilters,
+                   client: &Client,
+                   pagination: &Pagination) -> GhResult<Vec<Issue>> {
+    let request = requests::ListIssues {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        filters: filters.clone(),
+        pagination: pagination.clone()
+    };
+    pagination::send_matching_pages(client, request, pagination, |x| !is_pull_request(x))
+}
+
+// GitHub lists Pull Requests as Issues too, marked by a pull_request field.
+// They're dropped while paging, so they don't count towards --limit.
+fn is_pull_request(issue: &Value) -> bool {
+    match issue.get("pull_request") {
+        Some(pull_request) => !pull_request.is_null(),
+        None               => false
+    }
+}
+
+pub fn get_issue(owner: &str, repo: &str, number: u32, client: &Client) -> GhResult<Issue> {
+    client.send(requests::GetIssue {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        number: number
+    })
+}
+
+pub fn create_issue(owner: &str, repo: &str, issue: &NewIssue, client: &Client) -> GhResult<Issue> {
+    client.send(requests::CreateIssue {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        body: json_ops::to_json(issue, SERIALIZE_NEW_ISSUE)?
+    })
+}
+
+pub fn edit_issue(owner: &str,
+                  repo: &str,
+                  number: u32,
+                  issue: &EditIssue,
+                  client: &Client) -> GhResult<Issue> {
+    client.send(requests::EditIssue {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        number: number,
+        body: json_ops::to_json(issue, SERIALIZE_EDIT_ISSUE)?
+    })
+}
+
+pub fn list_comments(owner: &str,
+                     repo: &str,
+                     number: u32,
+                     client: &Client,
+                     pagination: &Pagination) -> GhResult<Vec<IssueComment>> {
+    let request = requests::ListComments {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        number: number,
+        pagination: pagination.clone()
+    };
+    pagination::send_all_pages(client, request, pagination)
+}
+
+pub fn create_comment(owner: &str,
+                      repo: &str,
+                      number: u32,
+                      body: &str,
+                      client: &Client) -> GhResult<IssueComment> {
+    let comment = NewIssueComment {
+        body: body.to_owned()
+    };
+    client.send(requests::CreateComment {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        number: number,
+        body: json_ops::to_json(&comment, SERIALIZE_NEW_ISSUE_COMMENT)?
+    })
+}
+
+// Adds labels to an Issue or Pull Request, returning all of its labels
+pub fn add_labels(owner: &str,
+                  repo: &str,
+                  number: u32,
+                  labels: &[String],
+                  client: &Client) -> GhResult<Vec<IssueLabel>> {
+    let labels = IssueLabels {
+        labels: labels.to_vec()
+    };
+    client.send(requests::AddLabels {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        number: number,
+        body: json_ops::to_json(&labels, SERIALIZE_ISSUE_LABELS)?
+    })
+}
+
+// Removes a label from an Issue or Pull Request, returning the labels left
+pub fn remove_label(owner: &str,
+                    repo: &str,
+                    number: u32,
+                    label: &str,
+                    client: &Client) -> GhResult<Vec<IssueLabel>> {
+    client.send(requests::RemoveLabel {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        number: number,
+        label: label.to_owned()
+    })
+}
+
+pub fn add_assignees(owner: &str,
+                     repo: &str,
+                     number: u32,
+                     assignees: &[String],
+                     client: &Client) -> GhResult<Issue> {
+    let assignees = IssueAssignees {
+        assignees: assignees.to_vec()
+    };
+    client.send(requests::ChangeAssignees {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        number: number,
+        method: Method::Post,
+        body: json_ops::to_json(&assignees, SERIALIZE_ISSUE_ASSIGNEES)?
+    })
+}
+
+pub fn remove_assignees(owner: &str,
+                        repo: &str,
+                        number: u32,
+                        assignees: &[String],
+                        client: &Client) -> GhResult<Issue> {
+    let assignees = IssueAssignees {
+        assignees: assignees.to_vec()
+    };
+    client.send(requests::ChangeAssignees {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        number: number,
+        method: Method::Delete,
+        body: json_ops::to_json(&assignees, SERIALIZE_ISSUE_ASSIGNEES)?
+    })
+}
+
+const SERIALIZE_NEW_ISSUE: &'static str = "Error serializing new GitHub Issue JSON.";
+const SERIALIZE_EDIT_ISSUE: &'static str = "Error serializing GitHub Issue edit JSON.";
+const SERIALIZE_NEW_ISSUE_COMMENT: &'static str = "Error serializing new GitHub Issue Comment JSON.";
+const SERIALIZE_ISSUE_LABELS: &'static str = "Error serializing GitHub Issue labels JSON.";
+const SERIALIZE_ISSUE_ASSIGNEES: &'static str = "Error serializing GitHub Issue assignees JSON.";
+
+mod requests {
+use config::Config;
+use error::{GhError, GhResult};
+use git_hub::{parse_url, GitHubRequest};
+use git_hub::issues::{Issue, IssueComment, IssueFilters, IssueLabel};
+use git_hub::pagination::Pagination;
+use hyper::Url;
+use hyper::method::Method;
+
+    fn issues_endpoint(config: &Config, owner: &str, repo: &str) -> String {
+        let mut endpoint = String::with_capacity(60);
+        endpoint.push_str(config.api_url());
+        endpoint.push_str("/repos/");
+        endpoint.push_str(owner);
+        endpoint.push_str("/");
+        endpoint.push_str(repo);
+        endpoint.push_str("/issues");
+        endpoint
+    }
+
+    fn issue_endpoint(config: &Config, owner: &str, repo: &str, number: u32) -> String {
+        let mut endpoint = issues_endpoint(config, owner, repo);
+        endpoint.push_str("/");
+        endpoint.push_str(&number.to_string());
+        endpoint
+    }
+
+    // Lists the Issues of a repository
+    // GET /repos/:owner/:repo/issues
+    // See https://developer.github.com/v3/issues/#list-issues-for-a-repository
+    // for more information
+    pub struct ListIssues {
+        pub owner: String,
+        pub repo: String,
+        pub filters: IssueFilters,
+        pub pagination: Pagination
+    }
+
+    impl GitHubRequest for ListIssues {
+        type Response = Vec<Issue>;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut url = parse_url(&issues_endpoint(config, &self.owner, &self.repo))?;
+            let filters = &self.filters;
+            let labels = if filters.labels.is_empty() { None } else { Some(filters.labels.join(",")) };
+            let query = vec![("state", filters.state.as_ref()),
+                             ("labels", labels.as_ref()),
+                             ("assignee", filters.assignee.as_ref()),
+                             ("milestone", filters.milestone.as_ref()),
+                             ("creator", filters.creator.as_ref()),
+                             ("since", filters.since.as_ref())];
+            for (name, value) in query {
+                if let Some(value) = value {
+                    url.query_pairs_mut().append_pair(name, value);
+                }
+            }
+            self.pagination.append_query(&mut url);
+            Ok(url)
+        }
+    }
+
+    // Gets a single Issue
+    // GET /repos/:owner/:repo/issues/:number
+    // See https://developer.github.com/v3/issues/#get-a-single-issue
+    // for more information
+    pub struct GetIssue {
+        pub owner: String,
+        pub repo: String,
+        pub number: u32
+    }
+
+    impl GitHubRequest for GetIssue {
+        type Response = Issue;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            parse_url(&issue_endpoint(config, &self.owner, &self.repo, self.number))
+        }
+    }
+
+    // Creates an Issue
+    // POST /repos/:owner/:repo/issues
+    // See https://developer.github.com/v3/issues/#create-an-issue
+    // for more information
+    pub struct CreateIssue {
+        pub owner: String,
+        pub repo: String,
+        pub body: String
+    }
+
+    impl GitHubRequest for CreateIssue {
+        type Response = Issue;
+
+        fn method(&self) -> Method {
+            Method::Post
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            parse_url(&issues_endpoint(config, &self.owner, &self.repo))
+        }
+
+        fn body(&self) -> Option<&str> {
+            Some(&self.body)
+        }
+    }
+
+    // Edits an Issue, e.g. to close or reopen it
+    // PATCH /repos/:owner/:repo/issues/:number
+    // See https://developer.github.com/v3/issues/#edit-an-issue
+    // for more information
+    pub struct EditIssue {
+        pub owner: String,
+        pub repo: String,
+        pub number: u32,
+        pub body: String
+    }
+
+    impl GitHubRequest for EditIssue {
+        type Response = Issue;
+
+        fn method(&self) -> Method {
+            Method::Patch
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            parse_url(&issue_endpoint(config, &self.owner, &self.repo, self.number))
+        }
+
+        fn body(&self) -> Option<&str> {
+            Some(&self.body)
+        }
+    }
+
+    // Lists the comments on an Issue
+    // GET /repos/:owner/:repo/issues/:number/comments
+    // See https://developer.github.com/v3/issues/comments/#list-comments-on-an-issue
+    // for more information
+    pub struct ListComments {
+        pub owner: String,
+        pub repo: String,
+        pub number: u32,
+        pub pagination: Pagination
+    }
+
+    impl GitHubRequest for ListComments {
+        type Response = Vec<IssueComment>;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = issue_endpoint(config, &self.owner, &self.repo, self.number);
+            endpoint.push_str("/comments");
+            let mut url = parse_url(&endpoint)?;
+            self.pagination.append_query(&mut url);
+            Ok(url)
+        }
+    }
+
+    // Comments on an Issue
+    // POST /repos/:owner/:repo/issues/:number/comments
+    // See https://developer.github.com/v3/issues/comments/#create-a-comment
+    // for more information
+    pub struct CreateComment {
+        pub owner: String,
+        pub repo: String,
+        pub number: u32,
+        pub body: String
+    }
+
+    impl GitHubRequest for CreateComment {
+        type Response = IssueComment;
+
+        fn method(&self) -> Method {
+            Method::Post
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = issue_endpoint(config, &self.owner, &self.repo, self.number);
+            endpoint.push_str("/comments");
+            parse_url(&endpoint)
+        }
+
+        fn body(&self) -> Option<&str> {
+            Some(&self.body)
+        }
+    }
+
+    // Adds labels to an Issue
+    // POST /repos/:owner/:repo/issues/:number/labels
+    // See https://developer.github.com/v3/issues/labels/#add-labels-to-an-issue
+    // for more information
+    pub struct AddLabels {
+        pub owner: String,
+        pub repo: String,
+        pub number: u32,
+        pub body: String
+    }
+
+    impl GitHubRequest for AddLabels {
+        type Response = Vec<IssueLabel>;
+
+        fn method(&self) -> Method {
+            Method::Post
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = issue_endpoint(config, &self.owner, &self.repo, self.number);
+            endpoint.push_str("/labels");
+            parse_url(&endpoint)
+        }
+
+        fn body(&self) -> Option<&str> {
+            Some(&self.body)
+        }
+    }
+
+    // Removes a label from an Issue
+    // DELETE /repos/:owner/:repo/issues/:number/labels/:name
+    // See https://developer.github.com/v3/issues/labels/#remove-a-label-from-an-issue
+    // for more information
+    pub struct RemoveLabel {
+        pub owner: String,
+        pub repo: String,
+        pub number: u32,
+        pub label: String
+    }
+
+    impl GitHubRequest for RemoveLabel {
+        type Response = Vec<IssueLabel>;
+
+        fn method(&self) -> Method {
+            Method::Delete
+        }
+
+        // Label names may hold spaces or slashes, so the name is pushed as
+        // an escaped path segment
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = issue_endpoint(config, &self.owner, &self.repo, self.number);
+            endpoint.push_str("/labels");
+            let mut url = parse_url(&endpoint)?;
+            url.path_segments_mut()
+               .map_err(|_| GhError::Usage(format!("hyper::Url could not add a label to URL {}", endpoint)))?
+               .push(&self.label);
+            Ok(url)
+        }
+    }
+
+    // Adds or removes the assignees of an Issue
+    // POST /repos/:owner/:repo/issues/:number/assignees
+    // DELETE /repos/:owner/:repo/issues/:number/assignees
+    // See https://developer.github.com/v3/issues/assignees/#add-assignees-to-an-issue
+    // for more information
+    pub struct ChangeAssignees {
+        pub owner: String,
+        pub repo: String,
+        pub number: u32,
+        pub method: Method,
+        pub body: String
+    }
+
+    impl GitHubRequest for ChangeAssignees {
+        type Response = Issue;
+
+        fn method(&self) -> Method {
+            self.method.clone()
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = issue_endpoint(config, &self.owner, &self.repo, self.number);
+            endpoint.push_str("/assignees");
+            parse_url(&endpoint)
+        }
+
+        fn body(&self) -> Option<&str> {
+            Some(&self.body)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+use config::Config;
+use evidence::json_ops;
+use git_hub::GitHubRequest;
+use git_hub::pagination::Pagination;
+use serde_json::Value;
+use super::{is_pull_request, Issue, IssueComment, IssueFilters};
+use super::requests::{ListIssues, RemoveLabel};
+
+    const ISSUE: &'static str = "{\"id\":1,\"number\":1347,\"state\":\"open\",\"title\":\"Found a bug\",\
+                                 \"body\":\"I'm having a problem with this.\",\"user\":{\"login\":\"octocat\",\"id\":1},\
+                                 \"labels\":[{\"name\":\"bug\",\"color\":\"f29513\",\"description\":null}],\
+                                 \"assignees\":[{\"login\":\"octocat\",\"id\":1}],\
+                                 \"milestone\":{\"number\":1,\"title\":\"v1.0\",\"state\":\"open\"},\"comments\":0,\
+                                 \"url\":\"https://api.github.com/repos/octocat/Hello-World/issues/1347\",\
+                                 \"html_url\":\"https://github.com/octocat/Hello-World/issues/1347\",\
+                                 \"created_at\":\"2011-04-22T13:33:48Z\",\"updated_at\":\"2011-04-22T13:33:48Z\",\
+                                 \"closed_at\":null,\
+                                 \"pull_request\":{\"url\":\"https://api.github.com/repos/octocat/Hello-World/pulls/1347\",\
+                                 \"html_url\":\"https://github.com/octocat/Hello-World/pull/1347\"}}";
+
+    const COMMENT: &'static str = "{\"id\":1,\"body\":\"Me too\",\"user\":{\"login\":\"octocat\",\"id\":1},\
+                                   \"html_url\":\"https://github.com/octocat/Hello-World/issues/1347#issuecomment-1\",\
+                                   \"created_at\":\"2011-04-14T16:00:49Z\",\"updated_at\":\"2011-04-14T16:00:49Z\"}";
+
+    fn build_test_config() -> Config {
+        Config {
+            username: "octocat".to_owned(),
+            access_token: "abcdefg1234567".to_owned(),
+            api_url: None,
+            credential_helper: None
+        }
+    }
+
+    fn list_issues(filters: IssueFilters) -> ListIssues {
+        ListIssues {
+            owner: "octocat".to_owned(),
+            repo: "Hello-World".to_owned(),
+            filters: filters,
+            pagination: Pagination::default()
+        }
+    }
+
+    #[test]
+    fn test_is_pull_request() -> () {
+        let mut issue: Value = json_ops::from_str(ISSUE, "").unwrap();
+        assert!(is_pull_request(&issue));
+        issue["pull_request"] = Value::Null;
+        assert!(!is_pull_request(&issue));
+        issue.as_object_mut().unwrap().remove("pull_request");
+        assert!(!is_pull_request(&issue));
+    }
+
+    #[test]
+    fn test_list_issues_url() -> () {
+        let filters = IssueFilters {
+            state: Some("closed".to_owned()),
+            labels: vec!["bug".to_owned(), "ui".to_owned()],
+            assignee: Some("none".to_owned()),
+            milestone: Some("3".to_owned()),
+            creator: Some("hubot".to_owned()),
+            since: Some("2017-06-01T00:00:00Z".to_owned())
+        };
+        let url = list_issues(filters).url(&build_test_config()).unwrap();
+        assert_eq!(url.as_str(),
+                   "https://api.github.com/repos/octocat/Hello-World/issues?state=closed&labels=bug%2Cui\
+                    &assignee=none&milestone=3&creator=hubot&since=2017-06-01T00%3A00%3A00Z");
+    }
+
+    #[test]
+    fn test_list_issues_url_no_filters() -> () {
+        let url = list_issues(IssueFilters::default()).url(&build_test_config()).unwrap();
+        assert_eq!(url.as_str(), "https://api.github.com/repos/octocat/Hello-World/issues");
+    }
+
+    #[test]
+    fn test_remove_label_url() -> () {
+        let request = RemoveLabel {
+            owner: "octocat".to_owned(),
+            repo: "Hello-World".to_owned(),
+            number: 1347,
+            label: "help wanted/ui".to_owned()
+        };
+        assert_eq!(request.url(&build_test_config()).unwrap().as_str(),
+                   "https://api.github.com/repos/octocat/Hello-World/issues/1347/labels/help%20wanted%2Fui");
+    }
+
+    quickcheck! {
+        fn prop_issue_optional_fields(choices: Vec<u8>, text: String) -> bool {
+            let nullable = ["body", "user", "milestone", "closed_at", "pull_request"];
+            let json = json_ops::vary_fields(ISSUE, &nullable, &["labels", "assignees"], &choices, &text);
+            json_ops::from_str::<Issue>(&json, "").is_ok()
+        }
+
+        fn prop_issue_comment_optional_fields(choices: Vec<u8>, text: String) -> bool {
+            let json = json_ops::vary_fields(COMMENT, &["user"], &[], &choices, &text);
+            json_ops::from_str::<IssueComment>(&json, "").is_ok()
+        }
+    }
+}
@@ -1,20 +1,98 @@
 use config::Config;
+use error::{ApiError, GhError, GhResult};
+use evidence::json_ops;
 
-use hyper::client::Request;
+use hyper::Url;
 use hyper::header::{Authorization, Bearer, Headers, Accept, qitem, UserAgent};
+use hyper::method::Method;
 use hyper::mime::{Mime, TopLevel, SubLevel, Attr, Value};
-use hyper::net::{Fresh, HttpsConnector};
+use hyper::net::HttpsConnector;
 use hyper::status::StatusCode;
 use hyper_native_tls::NativeTlsClient;
 
+use serde::de::DeserializeOwned;
+
+pub use self::client::Client;
+
+pub mod checks;
+pub mod client;
+pub mod graphql;
+pub mod issues;
 pub mod orgs;
+pub mod pagination;
+pub mod pulls;
+pub mod rate_limit;
+pub mod repos;
+pub mod reviews;
+pub mod teams;
+pub mod users;
 
-const URL: &'static str = "https://api.github.com";
+// The default GitHub API root, used unless the configuration names a
+// GitHub Enterprise Server instead.
+pub const URL: &'static str = "https://api.github.com";
 
+// A single GitHub API call, executed by a Client. Requests without a body
+// are GETs unless they say otherwise, and decode their successful response
+// from JSON into `Response`.
 pub trait GitHubRequest {
-    fn as_hyper_request(&self) -> Request<Fresh>;
+    type Response: DeserializeOwned;
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn url(&self, config: &Config) -> GhResult<Url>;
+
+    // The JSON body to send, if any
+    fn body(&self) -> Option<&str> {
+        None
+    }
+
+    // The media type to Accept instead of GitHub's v3 JSON, e.g.
+    // application/vnd.github.v3.diff for a Pull Request's diff.
+    // See https://developer.github.com/v3/media/
+    fn media_type(&self) -> Option<&str> {
+        None
+    }
+
+    // A response without a body, such as 204 No Content, decodes as JSON
+    // null, so that a request expecting nothing gets (), or None.
+    fn decode(&self, response: &GitHubResponse) -> GhResult<Self::Response> {
+        let body = match response.body {
+            Some(ref body) => body.as_str(),
+            None           => "null"
+        };
+        json_ops::from_str(body, DECODE_RESPONSE)
+    }
+}
+
+// Decodes a response to `request`, or GitHub's error JSON into a
+// GhError::Api when the status isn't 2xx.
+pub fn decode_response<R: GitHubRequest>(request: &R, response: &GitHubResponse) -> GhResult<R::Response> {
+    if response.status.is_success() {
+        request.decode(response)
+    } else {
+        Err(api_error(response))
+    }
 }
 
+pub fn api_error(response: &GitHubResponse) -> GhError {
+    let body = match response.body {
+        Some(ref body) => body.as_str(),
+        None           => ""
+    };
+    let error = json_ops::from_str(body, DECODE_RESPONSE).unwrap_or(ApiError {
+        message: UNEXPECTED_RESPONSE.to_owned(),
+        documentation_url: None,
+        errors: vec![]
+    });
+    GhError::Api(response.status, error)
+}
+
+const DECODE_RESPONSE: &'static str = "Error deserializing GitHub response JSON.";
+const UNEXPECTED_RESPONSE: &'static str = "GitHub responded without an error message.";
+
+// A response from GitHub. The body is None when GitHub sent an empty one.
 pub struct GitHubResponse {
     pub status: StatusCode,
     pub headers: Headers,
@@ -45,6 +123,13 @@ pub fn add_base_headers(headers: &mut Headers) -> () {
     headers.set(UserAgent(user_agent));
 }
 
+// Replaces the Accept header set by add_base_headers with `media_type`.
+pub fn set_media_type(headers: &mut Headers, media_type: &str) -> () {
+    if let Ok(mime) = media_type.parse::<Mime>() {
+        headers.set(Accept(vec![qitem(mime)]));
+    }
+}
+
 pub fn add_auth_header(headers: &mut Headers, config: &Config) -> () {
     headers.set(
         Authorization(
@@ -55,9 +140,25 @@ pub fn add_auth_header(headers: &mut Headers, config: &Config) -> () {
     );
 }
 
-pub fn connector() -> HttpsConnector<NativeTlsClient> {
-    let tls = NativeTlsClient::new().unwrap();
-    HttpsConnector::new(tls)
+pub fn connector() -> GhResult<HttpsConnector<NativeTlsClient>> {
+    match NativeTlsClient::new() {
+        Ok(tls) => Ok(HttpsConnector::new(tls)),
+        Err(e)  => Err(GhError::Network(format!("Could not initialize TLS. {}", e)))
+    }
+}
+
+// Returns a response header as a string, for headers hyper has no type for.
+pub fn header_value(headers: &Headers, name: &str) -> Option<String> {
+    headers.get_raw(name)
+           .and_then(|lines| lines.first())
+           .map(|line| String::from_utf8_lossy(line).trim().to_owned())
+}
+
+pub fn parse_url(endpoint: &str) -> GhResult<Url> {
+    match Url::parse(endpoint) {
+        Ok(url) => Ok(url),
+        Err(_)  => Err(GhError::Usage(format!("hyper::Url could not parse constructed URL {}", endpoint)))
+    }
 }
 
 #[cfg(test)]
@@ -69,9 +170,22 @@ use hyper_native_tls::NativeTlsClient;
 use hyper::net::Fresh;
 use hyper::client::Request;
 use hyper::method::Method;
-use super::{add_auth_header, add_base_headers, add_headers};
+use hyper::status::StatusCode;
+use super::{add_auth_header, add_base_headers, add_headers, decode_response, set_media_type};
+use super::{GitHubRequest, GitHubResponse};
 use hyper::mime::{Mime, TopLevel, SubLevel, Attr, Value};
 use config::Config;
+use error::GhResult;
+
+    struct TestRequest;
+
+    impl GitHubRequest for TestRequest {
+        type Response = ();
+
+        fn url(&self, _: &Config) -> GhResult<Url> {
+            Ok(Url::parse("https://api.github.com").unwrap())
+        }
+    }
 
     fn build_test_request() -> Request<Fresh> {
         let ssl = NativeTlsClient::new().unwrap();
@@ -87,7 +201,9 @@ use config::Config;
     fn build_test_config() -> Config {
         Config {
             username: "octocat".to_owned(),
-            access_token: "abcdefg1234567".to_owned()
+            access_token: "abcdefg1234567".to_owned(),
+            api_url: None,
+            credential_helper: None
         }
     }
 
@@ -135,6 +251,29 @@ use config::Config;
         test_authorization_header(headers, &config);
     }
 
+    #[test]
+    fn test_set_media_type() -> () {
+        let mut headers = Headers::new();
+        add_base_headers(&mut headers);
+        set_media_type(&mut headers, "application/vnd.github.v3.diff");
+        assert!(headers.get::<Accept>() == Some(
+            &Accept(vec![
+                qitem(Mime(TopLevel::Application, SubLevel::Ext("vnd.github.v3.diff".to_string()), vec![])),
+            ])
+        ));
+        assert!(headers.has::<UserAgent>() == true);
+    }
+
+    #[test]
+    fn test_decode_response_no_content() -> () {
+        let response = GitHubResponse {
+            status: StatusCode::NoContent,
+            headers: Headers::new(),
+            body: None
+        };
+        assert!(decode_response(&TestRequest, &response).is_ok());
+    }
+
     fn test_host_header(headers: &Headers) -> () {
         assert!(headers.has::<Host>() == true);
         assert!(headers.get::<Host>() == Some(&Host{
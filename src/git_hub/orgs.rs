@@ -1,97 +1,343 @@
-use config::Config;
+use error::GhResult;
 
-use git_hub;
-use git_hub::{GitHubRequest, GitHubResponse};
+use evidence::json_ops;
+use git_hub::Client;
+use git_hub::pagination;
+use git_hub::pagination::Pagination;
 
-use hyper::Url;
-use hyper::client::Request;
 use hyper::method::Method;
-use hyper::net::{Fresh, HttpsConnector};
-use hyper::status::StatusCode;
-
-use std::io::Read;
-use std::str::from_utf8;
 
 // GitHub OrgSummary. See https://developer.github.com/v3/orgs/
-// A base GitHub User
+// An Organization as listed. Most have no description, and only the login,
+// id and url are relied upon being sent.
 #[derive(Deserialize, Serialize)]
 pub struct OrgSummary {
     pub login: String,
-    pub id: u32,
+    pub id: u64,
     pub url: String,
+    #[serde(default)]
     pub repos_url: String,
+    #[serde(default)]
     pub events_url: String,
+    #[serde(default)]
     pub hooks_url: String,
+    #[serde(default)]
     pub issues_url: String,
+    #[serde(default)]
+    pub members_url: String,
+    #[serde(default)]
+    pub public_members_url: String,
+    #[serde(default)]
+    pub avatar_url: String,
+    #[serde(default)]
+    pub description: Option<String>
+}
+
+// A GitHub Organization's full profile. The plan, private repository
+// counts, billing email and member privileges are only sent to its owners.
+// See https://developer.github.com/v3/orgs/#get-an-organization
+#[derive(Deserialize, Serialize)]
+pub struct Organization {
+    pub login: String,
+    pub id: u64,
+    pub url: String,
+    pub html_url: String,
+    pub repos_url: String,
     pub members_url: String,
     pub public_members_url: String,
     pub avatar_url: String,
-    pub description: String
-}
-
-pub fn get_authed_user_orgs(config: &Config) -> GitHubResponse {
-    let request = requests::ListOrgs{
-        config: config.clone(),
-    }.as_hyper_request();
-    let mut response = request.start().unwrap().send().unwrap();
-    let mut body = vec![];
-    response.read_to_end(&mut body).unwrap();
-    let s: String = String::from_utf8_lossy(&body).into_owned();
-    let github_response = GitHubResponse {
-        status: response.status,
-        headers: response.headers.clone(),
-        body: Some(s),
+    pub description: Option<String>,
+    pub name: Option<String>,
+    pub company: Option<String>,
+    pub blog: Option<String>,
+    pub location: Option<String>,
+    pub email: Option<String>,
+    #[serde(default)]
+    pub is_verified: bool,
+    #[serde(default)]
+    pub public_repos: u32,
+    #[serde(default)]
+    pub public_gists: u32,
+    #[serde(default)]
+    pub followers: u32,
+    #[serde(default)]
+    pub following: u32,
+    pub created_at: String,
+    pub updated_at: String,
+    pub total_private_repos: Option<u32>,
+    pub owned_private_repos: Option<u32>,
+    pub collaborators: Option<u32>,
+    pub billing_email: Option<String>,
+    pub plan: Option<OrgPlan>,
+    pub default_repository_permission: Option<String>,
+    pub members_can_create_repositories: Option<bool>,
+    pub members_can_create_public_repositories: Option<bool>,
+    pub members_can_create_private_repositories: Option<bool>,
+    pub two_factor_requirement_enabled: Option<bool>
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct OrgPlan {
+    pub name: String,
+    pub space: u64,
+    pub private_repos: u32,
+    pub filled_seats: Option<u32>,
+    pub seats: Option<u32>
+}
+
+// The body of a request to edit an Organization, only the fields given are
+// changed.
+// See https://developer.github.com/v3/orgs/#edit-an-organization
+#[derive(Default, Deserialize, Serialize)]
+pub struct EditOrganization {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub billing_email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    // read, write, admin or none
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_repository_permission: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub members_can_create_repositories: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub members_can_create_public_repositories: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub members_can_create_private_repositories: Option<bool>
+}
+
+// A member of an Organization, as listed
+#[derive(Deserialize, Serialize)]
+pub struct OrgMember {
+    pub login: String,
+    pub id: u64,
+    #[serde(default)]
+    pub site_admin: bool
+}
+
+// A user's membership of an Organization.
+// See https://developer.github.com/v3/orgs/members/#get-organization-membership
+#[derive(Deserialize, Serialize)]
+pub struct OrgMembership {
+    // active, or pending until an invitation is accepted
+    pub state: String,
+    // admin or member
+    pub role: String,
+    pub organization_url: String,
+    pub user: OrgMember
+}
+
+#[derive(Deserialize, Serialize)]
+struct OrgMembershipRole {
+    role: String
+}
+
+// How to filter an Organization's members.
+//   filter - 2fa_disabled or all
+//   role   - all, admin or member
+#[derive(Clone, Default)]
+pub struct MemberFilters {
+    pub filter: Option<String>,
+    pub role: Option<String>
+}
+
+// A pending invitation to join an Organization. Users without an account
+// are invited by email, and have no login.
+// See https://developer.github.com/v3/orgs/members/#list-pending-organization-invitations
+#[derive(Deserialize, Serialize)]
+pub struct OrgInvitation {
+    pub id: u64,
+    pub login: Option<String>,
+    pub email: Option<String>,
+    // admin, direct_member or billing_manager
+    pub role: String,
+    pub created_at: String,
+    pub inviter: OrgMember,
+    #[serde(default)]
+    pub team_count: u32
+}
+
+// The body of a request to invite a user to an Organization, by their id or
+// email address.
+//   role     - admin, direct_member or billing_manager
+//   team_ids - the teams to add the user to once they accept
+// See https://developer.github.com/v3/orgs/members/#create-organization-invitation
+#[derive(Default, Deserialize, Serialize)]
+pub struct NewOrgInvitation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invitee_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub team_ids: Vec<u64>
+}
+
+pub fn get_authed_user_orgs(client: &Client, pagination: &Pagination) -> GhResult<Vec<OrgSummary>> {
+    let request = requests::ListOrgs {
+        pagination: pagination.clone()
     };
-    github_response
+    pagination::send_all_pages(client, request, pagination)
 }
 
-pub fn get_user_public_orgs(username: &str, config: &Config) -> GitHubResponse {
+pub fn get_user_public_orgs(username: &str,
+                            client: &Client,
+                            pagination: &Pagination) -> GhResult<Vec<OrgSummary>> {
     let request = requests::ListUserOrganizations {
         username: username.to_owned(),
-        config: config.clone()
-    }.as_hyper_request();
-    let mut response = request.start().unwrap().send().unwrap();
-    let mut body = vec![];
-    response.read_to_end(&mut body).unwrap();
-    let s: String = String::from_utf8_lossy(&body).into_owned();
-    let github_response = GitHubResponse {
-        status: response.status,
-        headers: response.headers.clone(),
-        body: Some(s),
+        pagination: pagination.clone()
+    };
+    pagination::send_all_pages(client, request, pagination)
+}
+
+pub fn get_org(org: &str, client: &Client) -> GhResult<Organization> {
+    client.send(requests::GetOrganization {
+        org: org.to_owned()
+    })
+}
+
+pub fn edit_org(org: &str, edit: &EditOrganization, client: &Client) -> GhResult<Organization> {
+    client.send(requests::EditOrganization {
+        org: org.to_owned(),
+        body: json_ops::to_json(edit, SERIALIZE_EDIT_ORGANIZATION)?
+    })
+}
+
+pub fn list_members(org: &str,
+                    filters: &MemberFilters,
+                    client: &Client,
+                    pagination: &Pagination) -> GhResult<Vec<OrgMember>> {
+    let request = requests::ListMembers {
+        org: org.to_owned(),
+        filters: filters.clone(),
+        pagination: pagination.clone()
+    };
+    pagination::send_all_pages(client, request, pagination)
+}
+
+pub fn get_membership(org: &str, username: &str, client: &Client) -> GhResult<OrgMembership> {
+    client.send(requests::Membership {
+        org: org.to_owned(),
+        username: username.to_owned(),
+        method: Method::Get,
+        body: None
+    })
+}
+
+// Invites a user to an Organization with `role`, admin or member, or
+// changes the role of an existing member.
+pub fn set_membership(org: &str, username: &str, role: &str, client: &Client) -> GhResult<OrgMembership> {
+    let role = OrgMembershipRole {
+        role: role.to_owned()
+    };
+    client.send(requests::Membership {
+        org: org.to_owned(),
+        username: username.to_owned(),
+        method: Method::Put,
+        body: Some(json_ops::to_json(&role, SERIALIZE_MEMBERSHIP_ROLE)?)
+    })
+}
+
+// Removes a user from an Organization and all of its teams
+pub fn remove_member(org: &str, username: &str, client: &Client) -> GhResult<()> {
+    client.send(requests::RemoveMember {
+        org: org.to_owned(),
+        username: username.to_owned()
+    })
+}
+
+pub fn list_invitations(org: &str, client: &Client, pagination: &Pagination) -> GhResult<Vec<OrgInvitation>> {
+    let request = requests::ListInvitations {
+        org: org.to_owned(),
+        pagination: pagination.clone()
+    };
+    pagination::send_all_pages(client, request, pagination)
+}
+
+pub fn create_invitation(org: &str, invitation: &NewOrgInvitation, client: &Client) -> GhResult<OrgInvitation> {
+    client.send(requests::CreateInvitation {
+        org: org.to_owned(),
+        body: json_ops::to_json(invitation, SERIALIZE_NEW_INVITATION)?
+    })
+}
+
+pub fn cancel_invitation(org: &str, invitation_id: u64, client: &Client) -> GhResult<()> {
+    client.send(requests::DeleteOrgResource {
+        org: org.to_owned(),
+        path: format!("/invitations/{}", invitation_id)
+    })
+}
+
+// Lists the collaborators on an Organization's repositories who aren't
+// members of it, optionally only those with `filter`, 2fa_disabled or all.
+pub fn list_outside_collaborators(org: &str,
+                                  filter: Option<&str>,
+                                  client: &Client,
+                                  pagination: &Pagination) -> GhResult<Vec<OrgMember>> {
+    let request = requests::ListOutsideCollaborators {
+        org: org.to_owned(),
+        filter: filter.map(|x| x.to_owned()),
+        pagination: pagination.clone()
     };
-    github_response
+    pagination::send_all_pages(client, request, pagination)
+}
+
+// Turns a member into an outside collaborator, keeping only their access to
+// the repositories their teams could reach
+pub fn convert_to_outside_collaborator(org: &str, username: &str, client: &Client) -> GhResult<()> {
+    client.send(requests::ConvertToOutsideCollaborator {
+        org: org.to_owned(),
+        username: username.to_owned()
+    })
+}
+
+// Removes an outside collaborator from every repository of an Organization
+pub fn remove_outside_collaborator(org: &str, username: &str, client: &Client) -> GhResult<()> {
+    client.send(requests::DeleteOrgResource {
+        org: org.to_owned(),
+        path: format!("/outside_collaborators/{}", username)
+    })
 }
 
+const SERIALIZE_EDIT_ORGANIZATION: &'static str = "Error serializing GitHub Organization edit JSON.";
+const SERIALIZE_MEMBERSHIP_ROLE: &'static str = "Error serializing GitHub Organization membership JSON.";
+const SERIALIZE_NEW_INVITATION: &'static str = "Error serializing new GitHub Organization invitation JSON.";
+
 mod requests {
 use config::Config;
-use git_hub;
-use git_hub::{add_headers, connector, GitHubRequest};
+use error::GhResult;
+use git_hub::{parse_url, GitHubRequest, GitHubResponse};
+use git_hub::orgs::{MemberFilters, OrgInvitation, OrgMember, OrgMembership, OrgSummary, Organization};
+use git_hub::pagination::Pagination;
 use hyper::Url;
-use hyper::client::Request;
 use hyper::method::Method;
-use hyper::net::{Fresh, HttpsConnector};
-use hyper::status::StatusCode;
 
+    fn org_endpoint(config: &Config, org: &str) -> String {
+        let mut endpoint = String::with_capacity(50);
+        endpoint.push_str(config.api_url());
+        endpoint.push_str("/orgs/");
+        endpoint.push_str(org);
+        endpoint
+    }
 
     // Lists organizations for the authenticated user
     // GET /user/orgs
     // See https://developer.github.com/v3/orgs/#list-your-organizations
     // for more information
     pub struct ListOrgs {
-        pub config: Config
+        pub pagination: Pagination
     }
 
     impl GitHubRequest for ListOrgs {
-        fn as_hyper_request(&self) -> Request<Fresh> {
-            let url = match Url::parse("https://api.github.com/user/orgs") {
-                Ok(url) => url,
-                Err(_)  => panic!("Could not parse foo bar"),
-            };
-            let mut req = Request::with_connector(Method::Get,
-                                                  url,
-                                                  &connector()).unwrap();
-            add_headers(req.headers_mut(), &self.config);
-            req
+        type Response = Vec<OrgSummary>;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = String::with_capacity(50);
+            endpoint.push_str(config.api_url());
+            endpoint.push_str("/user/orgs");
+            let mut url = parse_url(&endpoint)?;
+            self.pagination.append_query(&mut url);
+            Ok(url)
         }
     }
 
@@ -101,28 +347,361 @@ use hyper::status::StatusCode;
     // for more infromation
     pub struct ListUserOrganizations {
         pub username: String,
-        pub config: Config
+        pub pagination: Pagination
     }
 
     impl GitHubRequest for ListUserOrganizations {
-        fn as_hyper_request(&self) -> Request<Fresh> {
+        type Response = Vec<OrgSummary>;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
             let mut endpoint = String::with_capacity(50);
-            endpoint.push_str(git_hub::URL);
+            endpoint.push_str(config.api_url());
             endpoint.push_str("/users/");
             endpoint.push_str(&self.username);
             endpoint.push_str("/orgs");
-            let url = match Url::parse(&endpoint) {
-                Ok(url) => url,
-                Err(_)  => {
-                    let output = format!("hyper::Url could not parse constructed URL {}", endpoint);
-                    panic!(output)
+            let mut url = parse_url(&endpoint)?;
+            self.pagination.append_query(&mut url);
+            Ok(url)
+        }
+    }
+
+    // Gets an organization
+    // GET /orgs/:org
+    // See https://developer.github.com/v3/orgs/#get-an-organization
+    // for more information
+    pub struct GetOrganization {
+        pub org: String
+    }
+
+    impl GitHubRequest for GetOrganization {
+        type Response = Organization;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            parse_url(&org_endpoint(config, &self.org))
+        }
+    }
+
+    // Edits an organization
+    // PATCH /orgs/:org
+    // See https://developer.github.com/v3/orgs/#edit-an-organization
+    // for more information
+    pub struct EditOrganization {
+        pub org: String,
+        pub body: String
+    }
+
+    impl GitHubRequest for EditOrganization {
+        type Response = Organization;
+
+        fn method(&self) -> Method {
+            Method::Patch
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            parse_url(&org_endpoint(config, &self.org))
+        }
+
+        fn body(&self) -> Option<&str> {
+            Some(&self.body)
+        }
+    }
+
+    // Lists the members of an organization
+    // GET /orgs/:org/members
+    // See https://developer.github.com/v3/orgs/members/#members-list
+    // for more information
+    pub struct ListMembers {
+        pub org: String,
+        pub filters: MemberFilters,
+        pub pagination: Pagination
+    }
+
+    impl GitHubRequest for ListMembers {
+        type Response = Vec<OrgMember>;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = org_endpoint(config, &self.org);
+            endpoint.push_str("/members");
+            let mut url = parse_url(&endpoint)?;
+            {
+                let mut query = url.query_pairs_mut();
+                if let Some(ref filter) = self.filters.filter {
+                    query.append_pair("filter", filter);
+                }
+                if let Some(ref role) = self.filters.role {
+                    query.append_pair("role", role);
                 }
-            };
-            let mut req = Request::with_connector(Method::Get,
-                                                  url,
-                                                  &connector()).unwrap();
-            add_headers(req.headers_mut(), &self.config);
-            req
+            }
+            self.pagination.append_query(&mut url);
+            Ok(url)
+        }
+    }
+
+    // Gets, or with a role adds or updates, a user's membership
+    // GET /orgs/:org/memberships/:username
+    // PUT /orgs/:org/memberships/:username
+    // See https://developer.github.com/v3/orgs/members/#add-or-update-organization-membership
+    // for more information
+    pub struct Membership {
+        pub org: String,
+        pub username: String,
+        pub method: Method,
+        pub body: Option<String>
+    }
+
+    impl GitHubRequest for Membership {
+        type Response = OrgMembership;
+
+        fn method(&self) -> Method {
+            self.method.clone()
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = org_endpoint(config, &self.org);
+            endpoint.push_str("/memberships/");
+            endpoint.push_str(&self.username);
+            parse_url(&endpoint)
+        }
+
+        fn body(&self) -> Option<&str> {
+            self.body.as_ref().map(|x| x.as_str())
+        }
+    }
+
+    // Removes a member from an organization
+    // DELETE /orgs/:org/members/:username
+    // See https://developer.github.com/v3/orgs/members/#remove-a-member
+    // for more information
+    pub struct RemoveMember {
+        pub org: String,
+        pub username: String
+    }
+
+    impl GitHubRequest for RemoveMember {
+        type Response = ();
+
+        fn method(&self) -> Method {
+            Method::Delete
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = org_endpoint(config, &self.org);
+            endpoint.push_str("/members/");
+            endpoint.push_str(&self.username);
+            parse_url(&endpoint)
+        }
+
+        // 204 No Content
+        fn decode(&self, _: &GitHubResponse) -> GhResult<()> {
+            Ok(())
+        }
+    }
+
+    // Lists an organization's pending invitations
+    // GET /orgs/:org/invitations
+    // See https://developer.github.com/v3/orgs/members/#list-pending-organization-invitations
+    // for more information
+    pub struct ListInvitations {
+        pub org: String,
+        pub pagination: Pagination
+    }
+
+    impl GitHubRequest for ListInvitations {
+        type Response = Vec<OrgInvitation>;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = org_endpoint(config, &self.org);
+            endpoint.push_str("/invitations");
+            let mut url = parse_url(&endpoint)?;
+            self.pagination.append_query(&mut url);
+            Ok(url)
+        }
+    }
+
+    // Invites a user to an organization
+    // POST /orgs/:org/invitations
+    // See https://developer.github.com/v3/orgs/members/#create-organization-invitation
+    // for more information
+    pub struct CreateInvitation {
+        pub org: String,
+        pub body: String
+    }
+
+    impl GitHubRequest for CreateInvitation {
+        type Response = OrgInvitation;
+
+        fn method(&self) -> Method {
+            Method::Post
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = org_endpoint(config, &self.org);
+            endpoint.push_str("/invitations");
+            parse_url(&endpoint)
+        }
+
+        fn body(&self) -> Option<&str> {
+            Some(&self.body)
+        }
+    }
+
+    // Lists an organization's outside collaborators
+    // GET /orgs/:org/outside_collaborators
+    // See https://developer.github.com/v3/orgs/outside_collaborators/#list-outside-collaborators
+    // for more information
+    pub struct ListOutsideCollaborators {
+        pub org: String,
+        pub filter: Option<String>,
+        pub pagination: Pagination
+    }
+
+    impl GitHubRequest for ListOutsideCollaborators {
+        type Response = Vec<OrgMember>;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = org_endpoint(config, &self.org);
+            endpoint.push_str("/outside_collaborators");
+            let mut url = parse_url(&endpoint)?;
+            if let Some(ref filter) = self.filter {
+                url.query_pairs_mut().append_pair("filter", filter);
+            }
+            self.pagination.append_query(&mut url);
+            Ok(url)
+        }
+    }
+
+    // Converts an organization member to an outside collaborator
+    // PUT /orgs/:org/outside_collaborators/:username
+    // See https://developer.github.com/v3/orgs/outside_collaborators/#convert-member-to-outside-collaborator
+    // for more information
+    pub struct ConvertToOutsideCollaborator {
+        pub org: String,
+        pub username: String
+    }
+
+    impl GitHubRequest for ConvertToOutsideCollaborator {
+        type Response = ();
+
+        fn method(&self) -> Method {
+            Method::Put
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = org_endpoint(config, &self.org);
+            endpoint.push_str("/outside_collaborators/");
+            endpoint.push_str(&self.username);
+            parse_url(&endpoint)
+        }
+
+        // 202 Accepted or 204 No Content
+        fn decode(&self, _: &GitHubResponse) -> GhResult<()> {
+            Ok(())
+        }
+    }
+
+    // Cancels an invitation, or removes an outside collaborator
+    // DELETE /orgs/:org/invitations/:invitation_id
+    // DELETE /orgs/:org/outside_collaborators/:username
+    // See https://developer.github.com/v3/orgs/outside_collaborators/#remove-outside-collaborator
+    // for more information
+    pub struct DeleteOrgResource {
+        pub org: String,
+        pub path: String
+    }
+
+    impl GitHubRequest for DeleteOrgResource {
+        type Response = ();
+
+        fn method(&self) -> Method {
+            Method::Delete
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = org_endpoint(config, &self.org);
+            endpoint.push_str(&self.path);
+            parse_url(&endpoint)
+        }
+
+        // 204 No Content
+        fn decode(&self, _: &GitHubResponse) -> GhResult<()> {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+use evidence::json_ops;
+use serde_json;
+use super::{OrgInvitation, OrgSummary, Organization};
+
+    const ORG_SUMMARY: &'static str = "{\"login\":\"github\",\"id\":1,\"url\":\"https://api.github.com/orgs/github\",\
+                                       \"repos_url\":\"https://api.github.com/orgs/github/repos\",\
+                                       \"events_url\":\"https://api.github.com/orgs/github/events\",\
+                                       \"hooks_url\":\"https://api.github.com/orgs/github/hooks\",\
+                                       \"issues_url\":\"https://api.github.com/orgs/github/issues\",\
+                                       \"members_url\":\"https://api.github.com/orgs/github/members{/member}\",\
+                                       \"public_members_url\":\"https://api.github.com/orgs/github/public_members{/member}\",\
+                                       \"avatar_url\":\"https://github.com/images/error/octocat_happy.gif\",\
+                                       \"description\":\"A great organization\"}";
+
+    const ORGANIZATION: &'static str = "{\"login\":\"github\",\"id\":1,\"url\":\"https://api.github.com/orgs/github\",\
+                                        \"html_url\":\"https://github.com/github\",\"repos_url\":\"\",\"members_url\":\"\",\
+                                        \"public_members_url\":\"\",\"avatar_url\":\"\",\"description\":\"A great organization\",\
+                                        \"name\":\"github\",\"company\":\"GitHub\",\"blog\":\"https://github.com/blog\",\
+                                        \"location\":\"San Francisco\",\"email\":\"octocat@github.com\",\"is_verified\":true,\
+                                        \"public_repos\":2,\"public_gists\":1,\"followers\":20,\"following\":0,\
+                                        \"created_at\":\"2008-01-14T04:33:35Z\",\"updated_at\":\"2014-03-03T18:58:10Z\",\
+                                        \"total_private_repos\":100,\"owned_private_repos\":100,\"collaborators\":8,\
+                                        \"billing_email\":\"mona@github.com\",\
+                                        \"plan\":{\"name\":\"Medium\",\"space\":400,\"private_repos\":20,\"filled_seats\":4,\"seats\":5},\
+                                        \"default_repository_permission\":\"read\",\"members_can_create_repositories\":true,\
+                                        \"members_can_create_public_repositories\":false,\
+                                        \"members_can_create_private_repositories\":true,\
+                                        \"two_factor_requirement_enabled\":true}";
+
+    const ORGANIZATION_NULLABLE: [&'static str; 16] = ["description", "name", "company", "blog", "location", "email",
+                                                       "total_private_repos", "owned_private_repos", "collaborators",
+                                                       "billing_email", "plan", "default_repository_permission",
+                                                       "members_can_create_repositories",
+                                                       "members_can_create_public_repositories",
+                                                       "members_can_create_private_repositories",
+                                                       "two_factor_requirement_enabled"];
+
+    const INVITATION: &'static str = "{\"id\":1,\"login\":\"monalisa\",\"email\":\"octocat@github.com\",\
+                                      \"role\":\"direct_member\",\"created_at\":\"2016-11-30T06:46:10-08:00\",\
+                                      \"inviter\":{\"login\":\"other_user\",\"id\":2,\"site_admin\":false},\
+                                      \"team_count\":2}";
+
+    #[test]
+    fn test_org_summary_null_description() -> () {
+        let json = json_ops::vary_fields(ORG_SUMMARY, &["description"], &[], &[1], "");
+        let org: OrgSummary = json_ops::from_str(&json, "").unwrap();
+        assert_eq!(org.description, None);
+    }
+
+    quickcheck! {
+        fn prop_org_summary_optional_fields(choices: Vec<u8>, text: String) -> bool {
+            let omittable = ["repos_url", "events_url", "hooks_url", "issues_url", "members_url", "public_members_url",
+                             "avatar_url"];
+            let json = json_ops::vary_fields(ORG_SUMMARY, &["description"], &omittable, &choices, &text);
+            json_ops::from_str::<OrgSummary>(&json, "").is_ok()
+        }
+
+        fn prop_org_summary_description(description: Option<String>) -> bool {
+            let json = ORG_SUMMARY.replace("\"A great organization\"", &serde_json::to_string(&description).unwrap());
+            json_ops::from_str::<OrgSummary>(&json, "").unwrap().description == description
+        }
+
+        fn prop_organization_optional_fields(choices: Vec<u8>, text: String) -> bool {
+            let omittable = ["is_verified", "public_repos", "public_gists", "followers", "following"];
+            let json = json_ops::vary_fields(ORGANIZATION, &ORGANIZATION_NULLABLE, &omittable, &choices, &text);
+            json_ops::from_str::<Organization>(&json, "").is_ok()
+        }
+
+        fn prop_invitation_optional_fields(choices: Vec<u8>, text: String) -> bool {
+            let json = json_ops::vary_fields(INVITATION, &["login", "email"], &["team_count"], &choices, &text);
+            json_ops::from_str::<OrgInvitation>(&json, "").is_ok()
         }
     }
 }
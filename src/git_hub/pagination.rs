@@ -0,0 +1,267 @@
+use error::{GhError, GhResult};
+
+use git_hub::{decode_response, parse_url, Client, GitHubRequest, GitHubResponse};
+
+use hyper::Url;
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use serde_json;
+use serde_json::Value;
+
+// The largest page size GitHub will honour.
+// See https://developer.github.com/v3/#pagination
+pub const MAX_PER_PAGE: u32 = 100;
+
+// How a list endpoint should be paged through.
+//   per_page - the number of results GitHub returns per page
+//   page     - fetch only this page rather than following `Link` headers
+//   limit    - stop once this many results have been collected
+#[derive(Clone, Default)]
+pub struct Pagination {
+    pub per_page: Option<u32>,
+    pub page: Option<u32>,
+    pub limit: Option<usize>
+}
+
+impl Pagination {
+    // Appends `per_page` and `page` query parameters to a list endpoint URL.
+    // When only a limit is given, the page size is shrunk to fit it so small
+    // limits don't download a full page of results.
+    pub fn append_query(&self, url: &mut Url) -> () {
+        let per_page = match (self.per_page, self.limit) {
+            (Some(per_page), _) => Some(per_page),
+            (None, Some(limit)) => Some(::std::cmp::min(limit as u32, MAX_PER_PAGE)),
+            (None, None)        => None
+        };
+        if let Some(per_page) = per_page {
+            url.query_pairs_mut().append_pair("per_page", &per_page.to_string());
+        }
+        if let Some(page) = self.page {
+            url.query_pairs_mut().append_pair("page", &page.to_string());
+        }
+    }
+}
+
+// Executes a list request, following `Link: <...>; rel="next"` headers until
+// GitHub runs out of pages or the pagination limit is reached. The JSON array
+// of every page is concatenated into the body of a single GitHubResponse.
+// The first non 200 OK response is returned as-is.
+pub fn get_all_pages<R: GitHubRequest>(client: &Client,
+                                       request: &R,
+                                       pagination: &Pagination) -> GhResult<GitHubResponse> {
+    get_matching_pages(client, request, pagination, |_| true)
+}
+
+// Like get_all_pages, but only the items `keep` returns true for are
+// collected, so the ones dropped don't count towards the pagination limit.
+pub fn get_matching_pages<R, F>(client: &Client,
+                                request: &R,
+                                pagination: &Pagination,
+                                keep: F) -> GhResult<GitHubResponse>
+    where R: GitHubRequest, F: Fn(&Value) -> bool {
+    let first = client.execute(request)?;
+    if first.status != StatusCode::Ok || pagination.page.is_some() {
+        return truncate(first, pagination.limit, &keep);
+    }
+
+    let headers = first.headers.clone();
+    let mut items: Vec<Value> = vec![];
+    let mut response = first;
+    loop {
+        match response.body {
+            Some(ref body) => match serde_json::from_str::<Vec<Value>>(body) {
+                Ok(page) => items.extend(page.into_iter().filter(|x| keep(x))),
+                Err(_)   => return Ok(GitHubResponse {
+                    status: response.status,
+                    headers: response.headers.clone(),
+                    body: Some(body.to_owned())
+                }),
+            },
+            None => break,
+        }
+        if limit_reached(items.len(), pagination.limit) {
+            break;
+        }
+        let next = match next_link(&response.headers) {
+            Some(next) => next,
+            None       => break,
+        };
+        response = client.execute_url(Method::Get, &parse_url(&next)?, None)?;
+        if response.status != StatusCode::Ok {
+            return Ok(response);
+        }
+    }
+    if let Some(limit) = pagination.limit {
+        items.truncate(limit);
+    }
+    Ok(GitHubResponse {
+        status: StatusCode::Ok,
+        headers: headers,
+        body: Some(join_pages(&items)?)
+    })
+}
+
+// Like Client::send, but for list requests: every page is fetched as in
+// get_all_pages and decoded together.
+pub fn send_all_pages<R: GitHubRequest>(client: &Client,
+                                        request: R,
+                                        pagination: &Pagination) -> GhResult<R::Response> {
+    decode_response(&request, &get_all_pages(client, &request, pagination)?)
+}
+
+// Like send_all_pages, but for the items `keep` returns true for, see
+// get_matching_pages.
+pub fn send_matching_pages<R, F>(client: &Client,
+                                 request: R,
+                                 pagination: &Pagination,
+                                 keep: F) -> GhResult<R::Response>
+    where R: GitHubRequest, F: Fn(&Value) -> bool {
+    decode_response(&request, &get_matching_pages(client, &request, pagination, keep)?)
+}
+
+// Parses the URL of the next page out of GitHub's `Link` header, e.g.
+// <https://api.github.com/user/orgs?page=2>; rel="next", <https://api.github.com/user/orgs?page=5>; rel="last"
+pub fn next_link(headers: &Headers) -> Option<String> {
+    let raw = match headers.get_raw("Link") {
+        Some(raw) => raw,
+        None      => return None,
+    };
+    for line in raw {
+        let value = String::from_utf8_lossy(line);
+        for link in value.split(',') {
+            let mut parts = link.split(';');
+            let target = match parts.next() {
+                Some(target) => target.trim(),
+                None         => continue,
+            };
+            let is_next = parts.any(|param| {
+                let param = param.trim();
+                param == "rel=\"next\"" || param == "rel=next"
+            });
+            if is_next && target.starts_with('<') && target.ends_with('>') {
+                return Some(target[1..target.len() - 1].to_owned());
+            }
+        }
+    }
+    None
+}
+
+fn limit_reached(count: usize, limit: Option<usize>) -> bool {
+    match limit {
+        Some(limit) => count >= limit,
+        None        => false
+    }
+}
+
+// Drops the items of a single page `keep` returns false for, then those
+// past `limit`.
+fn truncate<F>(response: GitHubResponse, limit: Option<usize>, keep: &F) -> GhResult<GitHubResponse>
+    where F: Fn(&Value) -> bool {
+    let body = match response.body {
+        Some(ref body) => match serde_json::from_str::<Vec<Value>>(body) {
+            Ok(mut items) => {
+                items.retain(|x| keep(x));
+                if let Some(limit) = limit {
+                    items.truncate(limit);
+                }
+                Some(join_pages(&items)?)
+            },
+            Err(_) => Some(body.to_owned()),
+        },
+        None => None,
+    };
+    Ok(GitHubResponse {
+        status: response.status,
+        headers: response.headers,
+        body: body
+    })
+}
+
+fn join_pages(items: &[Value]) -> GhResult<String> {
+    serde_json::to_string(items).map_err(|e| GhError::Json(JOIN_PAGES.to_owned(), e))
+}
+
+const JOIN_PAGES: &'static str = "Error serializing paginated GitHub JSON.";
+
+#[cfg(test)]
+mod tests {
+use git_hub::GitHubResponse;
+use hyper::Url;
+use hyper::header::Headers;
+use hyper::status::StatusCode;
+use serde_json::Value;
+use super::{next_link, truncate, Pagination};
+
+    fn link_headers(value: &str) -> Headers {
+        let mut headers = Headers::new();
+        headers.set_raw("Link", vec![value.as_bytes().to_vec()]);
+        headers
+    }
+
+    #[test]
+    fn test_next_link() -> () {
+        let headers = link_headers("<https://api.github.com/user/orgs?page=2>; rel=\"next\", \
+                                    <https://api.github.com/user/orgs?page=5>; rel=\"last\"");
+        assert_eq!(next_link(&headers),
+                   Some("https://api.github.com/user/orgs?page=2".to_owned()));
+    }
+
+    #[test]
+    fn test_next_link_last_page() -> () {
+        let headers = link_headers("<https://api.github.com/user/orgs?page=1>; rel=\"first\", \
+                                    <https://api.github.com/user/orgs?page=4>; rel=\"prev\"");
+        assert_eq!(next_link(&headers), None);
+    }
+
+    #[test]
+    fn test_next_link_no_header() -> () {
+        assert_eq!(next_link(&Headers::new()), None);
+    }
+
+    #[test]
+    fn test_append_query() -> () {
+        let mut url = Url::parse("https://api.github.com/user/orgs").unwrap();
+        let pagination = Pagination { per_page: Some(50), page: Some(3), limit: None };
+        pagination.append_query(&mut url);
+        assert_eq!(url.as_str(), "https://api.github.com/user/orgs?per_page=50&page=3");
+    }
+
+    #[test]
+    fn test_append_query_limit_shrinks_page() -> () {
+        let mut url = Url::parse("https://api.github.com/user/orgs").unwrap();
+        let pagination = Pagination { per_page: None, page: None, limit: Some(5) };
+        pagination.append_query(&mut url);
+        assert_eq!(url.as_str(), "https://api.github.com/user/orgs?per_page=5");
+    }
+
+    #[test]
+    fn test_append_query_none() -> () {
+        let mut url = Url::parse("https://api.github.com/user/orgs").unwrap();
+        Pagination::default().append_query(&mut url);
+        assert_eq!(url.as_str(), "https://api.github.com/user/orgs");
+    }
+
+    #[test]
+    fn test_truncate() -> () {
+        let response = GitHubResponse {
+            status: StatusCode::Ok,
+            headers: Headers::new(),
+            body: Some("[1,2,3,4]".to_owned())
+        };
+        let truncated = truncate(response, Some(2), &|_: &Value| true).unwrap();
+        assert_eq!(truncated.body, Some("[1,2]".to_owned()));
+    }
+
+    #[test]
+    fn test_truncate_keeps_matching() -> () {
+        let response = GitHubResponse {
+            status: StatusCode::Ok,
+            headers: Headers::new(),
+            body: Some("[1,2,3,4,5]".to_owned())
+        };
+        let truncated = truncate(response, Some(2), &|x: &Value| x.as_u64() != Some(2)).unwrap();
+        assert_eq!(truncated.body, Some("[1,3]".to_owned()));
+    }
+}
@@ -0,0 +1,607 @@
+use error::GhResult;
+
+use evidence::json_ops;
+use git_hub::{graphql, Client};
+use git_hub::pagination;
+use git_hub::pagination::Pagination;
+
+use hyper::method::Method;
+
+// GitHub Pull Request summary, as returned when listing a repository's
+// Pull Requests. See https://developer.github.com/v3/pulls/
+#[derive(Deserialize, Serialize)]
+pub struct PullRequestSummary {
+    pub id: u64,
+    pub number: u32,
+    pub state: String,
+    pub title: String,
+    pub url: String,
+    pub html_url: String,
+    // null once the author's account has been deleted
+    pub user: Option<PullRequestUser>,
+    pub head: PullRequestRef,
+    pub base: PullRequestRef,
+    pub body: Option<String>,
+    pub draft: Option<bool>,
+    pub created_at: String,
+    pub updated_at: String
+}
+
+// A single GitHub Pull Request, with the merge and review details GitHub
+// only computes when one is fetched by number.
+// See https://developer.github.com/v3/pulls/#get-a-single-pull-request
+#[derive(Deserialize, Serialize)]
+pub struct PullRequest {
+    pub id: u64,
+    // The GraphQL id, needed to move the Pull Request in and out of draft
+    #[serde(default)]
+    pub node_id: String,
+    pub number: u32,
+    pub state: String,
+    pub title: String,
+    pub url: String,
+    pub html_url: String,
+    // null once the author's account has been deleted
+    pub user: Option<PullRequestUser>,
+    pub head: PullRequestRef,
+    pub base: PullRequestRef,
+    pub body: Option<String>,
+    pub draft: Option<bool>,
+    #[serde(default)]
+    pub merged: bool,
+    // null while GitHub is still working out whether it merges cleanly
+    pub mergeable: Option<bool>,
+    pub mergeable_state: Option<String>,
+    pub merged_by: Option<PullRequestUser>,
+    #[serde(default)]
+    pub requested_reviewers: Vec<PullRequestUser>,
+    #[serde(default)]
+    pub requested_teams: Vec<PullRequestTeam>,
+    #[serde(default)]
+    pub comments: u32,
+    #[serde(default)]
+    pub review_comments: u32,
+    #[serde(default)]
+    pub commits: u32,
+    #[serde(default)]
+    pub additions: u32,
+    #[serde(default)]
+    pub deletions: u32,
+    #[serde(default)]
+    pub changed_files: u32,
+    pub created_at: String,
+    pub updated_at: String,
+    pub closed_at: Option<String>,
+    pub merged_at: Option<String>
+}
+
+// The GitHub User who opened a Pull Request
+#[derive(Deserialize, Serialize)]
+pub struct PullRequestUser {
+    pub login: String,
+    pub id: u64
+}
+
+// A team asked to review a Pull Request
+#[derive(Deserialize, Serialize)]
+pub struct PullRequestTeam {
+    pub name: String,
+    pub slug: String
+}
+
+// The head or base branch of a Pull Request
+#[derive(Deserialize, Serialize)]
+pub struct PullRequestRef {
+    pub label: String,
+    #[serde(rename = "ref")]
+    pub ref_name: String,
+    pub sha: String,
+    // null once the head repository of a fork has been deleted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<PullRequestRepo>
+}
+
+// The repository a Pull Request branch lives in
+#[derive(Deserialize, Serialize)]
+pub struct PullRequestRepo {
+    pub full_name: String,
+    pub clone_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_branch: Option<String>
+}
+
+// The body of a request to open a new Pull Request.
+// See https://developer.github.com/v3/pulls/#create-a-pull-request
+#[derive(Deserialize, Serialize)]
+pub struct NewPullRequest {
+    pub title: String,
+    pub head: String,
+    pub base: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub draft: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maintainer_can_modify: Option<bool>
+}
+
+// The body of a request to merge a Pull Request. GitHub writes its own
+// commit title and message for any left out.
+// See https://developer.github.com/v3/pulls/#merge-a-pull-request-merge-button
+#[derive(Default, Deserialize, Serialize)]
+pub struct MergePullRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_message: Option<String>,
+    // The SHA the head must still be at, so a push made after reviewing
+    // isn't merged unseen
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha: Option<String>,
+    // merge, squash or rebase
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merge_method: Option<String>
+}
+
+// The body of a request to edit a Pull Request, only the fields given are
+// changed.
+// See https://developer.github.com/v3/pulls/#update-a-pull-request
+#[derive(Default, Deserialize, Serialize)]
+pub struct EditPullRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    // open or closed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>
+}
+
+// Users, by login, and teams, by slug, to request or remove reviews from.
+// GitHub requires `reviewers` when removing, even if only teams are.
+// See https://developer.github.com/v3/pulls/review_requests/
+#[derive(Default, Deserialize, Serialize)]
+pub struct ReviewRequest {
+    pub reviewers: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub team_reviewers: Vec<String>
+}
+
+#[derive(Serialize)]
+struct PullRequestId<'a> {
+    id: &'a str
+}
+
+// GitHub's answer to a successful merge
+#[derive(Deserialize, Serialize)]
+pub struct MergeResult {
+    pub sha: String,
+    pub merged: bool,
+    pub message: String
+}
+
+pub fn get_pull_requests(owner: &str,
+                         repo: &str,
+                         state: Option<&str>,
+                         client: &Client,
+                         pagination: &Pagination) -> GhResult<Vec<PullRequestSummary>> {
+    let request = requests::ListPullRequests {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        state: state.map(|s| s.to_owned()),
+        pagination: pagination.clone()
+    };
+    pagination::send_all_pages(client, request, pagination)
+}
+
+pub fn create_pull_request(owner: &str,
+                           repo: &str,
+                           pull_request: &NewPullRequest,
+                           client: &Client) -> GhResult<PullRequestSummary> {
+    let request = requests::CreatePullRequest {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        body: json_ops::to_json(pull_request, SERIALIZE_NEW_PULL_REQUEST)?
+    };
+    client.send(request)
+}
+
+pub fn get_pull_request(owner: &str, repo: &str, number: u32, client: &Client) -> GhResult<PullRequest> {
+    client.send(requests::GetPullRequest {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        number: number
+    })
+}
+
+// The unified diff of a Pull Request against its base branch
+pub fn get_pull_request_diff(owner: &str, repo: &str, number: u32, client: &Client) -> GhResult<String> {
+    client.send(requests::GetPullRequestDiff {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        number: number
+    })
+}
+
+pub fn merge_pull_request(owner: &str,
+                          repo: &str,
+                          number: u32,
+                          merge: &MergePullRequest,
+                          client: &Client) -> GhResult<MergeResult> {
+    client.send(requests::MergePullRequest {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        number: number,
+        body: json_ops::to_json(merge, SERIALIZE_MERGE_PULL_REQUEST)?
+    })
+}
+
+pub fn edit_pull_request(owner: &str,
+                         repo: &str,
+                         number: u32,
+                         pull_request: &EditPullRequest,
+                         client: &Client) -> GhResult<PullRequest> {
+    client.send(requests::EditPullRequest {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        number: number,
+        body: json_ops::to_json(pull_request, SERIALIZE_EDIT_PULL_REQUEST)?
+    })
+}
+
+pub fn request_reviewers(owner: &str,
+                         repo: &str,
+                         number: u32,
+                         review_request: &ReviewRequest,
+                         client: &Client) -> GhResult<PullRequest> {
+    client.send(requests::ChangeReviewRequests {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        number: number,
+        method: Method::Post,
+        body: json_ops::to_json(review_request, SERIALIZE_REVIEW_REQUEST)?
+    })
+}
+
+pub fn remove_requested_reviewers(owner: &str,
+                                  repo: &str,
+                                  number: u32,
+                                  review_request: &ReviewRequest,
+                                  client: &Client) -> GhResult<PullRequest> {
+    client.send(requests::ChangeReviewRequests {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        number: number,
+        method: Method::Delete,
+        body: json_ops::to_json(review_request, SERIALIZE_REVIEW_REQUEST)?
+    })
+}
+
+// Converts a Pull Request to a draft, or marks a draft ready for review.
+// The REST API can't do either, so this goes through GraphQL with the Pull
+// Request's node_id.
+pub fn set_draft(node_id: &str, draft: bool, client: &Client) -> GhResult<()> {
+    let mutation = if draft { CONVERT_TO_DRAFT } else { READY_FOR_REVIEW };
+    graphql::query(mutation, PullRequestId { id: node_id }, client).map(|_| ())
+}
+
+const CONVERT_TO_DRAFT: &'static str =
+    "mutation($id: ID!) { convertPullRequestToDraft(input: {pullRequestId: $id}) { pullRequest { isDraft } } }";
+const READY_FOR_REVIEW: &'static str =
+    "mutation($id: ID!) { markPullRequestReadyForReview(input: {pullRequestId: $id}) { pullRequest { isDraft } } }";
+
+const SERIALIZE_NEW_PULL_REQUEST: &'static str = "Error serializing new GitHub Pull Request JSON.";
+const SERIALIZE_MERGE_PULL_REQUEST: &'static str = "Error serializing GitHub Pull Request merge JSON.";
+const SERIALIZE_EDIT_PULL_REQUEST: &'static str = "Error serializing GitHub Pull Request edit JSON.";
+const SERIALIZE_REVIEW_REQUEST: &'static str = "Error serializing GitHub review request JSON.";
+
+pub(crate) mod requests {
+use config::Config;
+use error::GhResult;
+use git_hub::{parse_url, GitHubRequest, GitHubResponse};
+use git_hub::pagination::Pagination;
+use git_hub::pulls::{MergeResult, PullRequest, PullRequestSummary};
+use hyper::Url;
+use hyper::method::Method;
+
+    fn pulls_endpoint(config: &Config, owner: &str, repo: &str) -> String {
+        let mut endpoint = String::with_capacity(60);
+        endpoint.push_str(config.api_url());
+        endpoint.push_str("/repos/");
+        endpoint.push_str(owner);
+        endpoint.push_str("/");
+        endpoint.push_str(repo);
+        endpoint.push_str("/pulls");
+        endpoint
+    }
+
+    // Also the base of the review endpoints in git_hub::reviews
+    pub(crate) fn pull_endpoint(config: &Config, owner: &str, repo: &str, number: u32) -> String {
+        let mut endpoint = pulls_endpoint(config, owner, repo);
+        endpoint.push_str("/");
+        endpoint.push_str(&number.to_string());
+        endpoint
+    }
+
+    // Lists the Pull Requests of a repository
+    // GET /repos/:owner/:repo/pulls
+    // See https://developer.github.com/v3/pulls/#list-pull-requests
+    // for more information
+    pub struct ListPullRequests {
+        pub owner: String,
+        pub repo: String,
+        pub state: Option<String>,
+        pub pagination: Pagination
+    }
+
+    impl GitHubRequest for ListPullRequests {
+        type Response = Vec<PullRequestSummary>;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut url = parse_url(&pulls_endpoint(config, &self.owner, &self.repo))?;
+            if let Some(ref state) = self.state {
+                url.query_pairs_mut().append_pair("state", state);
+            }
+            self.pagination.append_query(&mut url);
+            Ok(url)
+        }
+    }
+
+    // Creates a Pull Request
+    // POST /repos/:owner/:repo/pulls
+    // See https://developer.github.com/v3/pulls/#create-a-pull-request
+    // for more information
+    pub struct CreatePullRequest {
+        pub owner: String,
+        pub repo: String,
+        pub body: String
+    }
+
+    impl GitHubRequest for CreatePullRequest {
+        type Response = PullRequestSummary;
+
+        fn method(&self) -> Method {
+            Method::Post
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            parse_url(&pulls_endpoint(config, &self.owner, &self.repo))
+        }
+
+        fn body(&self) -> Option<&str> {
+            Some(&self.body)
+        }
+    }
+
+    // Gets a single Pull Request
+    // GET /repos/:owner/:repo/pulls/:number
+    // See https://developer.github.com/v3/pulls/#get-a-single-pull-request
+    // for more information
+    pub struct GetPullRequest {
+        pub owner: String,
+        pub repo: String,
+        pub number: u32
+    }
+
+    impl GitHubRequest for GetPullRequest {
+        type Response = PullRequest;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            parse_url(&pull_endpoint(config, &self.owner, &self.repo, self.number))
+        }
+    }
+
+    // Gets the diff of a single Pull Request
+    // GET /repos/:owner/:repo/pulls/:number
+    // Accept: application/vnd.github.v3.diff
+    // See https://developer.github.com/v3/media/#commits-commit-comparison-and-pull-requests
+    // for more information
+    pub struct GetPullRequestDiff {
+        pub owner: String,
+        pub repo: String,
+        pub number: u32
+    }
+
+    impl GitHubRequest for GetPullRequestDiff {
+        type Response = String;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            parse_url(&pull_endpoint(config, &self.owner, &self.repo, self.number))
+        }
+
+        fn media_type(&self) -> Option<&str> {
+            Some("application/vnd.github.v3.diff")
+        }
+
+        // The diff is plain text, not JSON
+        fn decode(&self, response: &GitHubResponse) -> GhResult<String> {
+            Ok(response.body.clone().unwrap_or(String::new()))
+        }
+    }
+
+    // Merges a Pull Request
+    // PUT /repos/:owner/:repo/pulls/:number/merge
+    // See https://developer.github.com/v3/pulls/#merge-a-pull-request-merge-button
+    // for more information
+    pub struct MergePullRequest {
+        pub owner: String,
+        pub repo: String,
+        pub number: u32,
+        pub body: String
+    }
+
+    impl GitHubRequest for MergePullRequest {
+        type Response = MergeResult;
+
+        fn method(&self) -> Method {
+            Method::Put
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = pull_endpoint(config, &self.owner, &self.repo, self.number);
+            endpoint.push_str("/merge");
+            parse_url(&endpoint)
+        }
+
+        fn body(&self) -> Option<&str> {
+            Some(&self.body)
+        }
+    }
+
+    // Edits a Pull Request
+    // PATCH /repos/:owner/:repo/pulls/:number
+    // See https://developer.github.com/v3/pulls/#update-a-pull-request
+    // for more information
+    pub struct EditPullRequest {
+        pub owner: String,
+        pub repo: String,
+        pub number: u32,
+        pub body: String
+    }
+
+    impl GitHubRequest for EditPullRequest {
+        type Response = PullRequest;
+
+        fn method(&self) -> Method {
+            Method::Patch
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            parse_url(&pull_endpoint(config, &self.owner, &self.repo, self.number))
+        }
+
+        fn body(&self) -> Option<&str> {
+            Some(&self.body)
+        }
+    }
+
+    // Requests or removes reviews of a Pull Request
+    // POST /repos/:owner/:repo/pulls/:number/requested_reviewers
+    // DELETE /repos/:owner/:repo/pulls/:number/requested_reviewers
+    // See https://developer.github.com/v3/pulls/review_requests/
+    // for more information
+    pub struct ChangeReviewRequests {
+        pub owner: String,
+        pub repo: String,
+        pub number: u32,
+        pub method: Method,
+        pub body: String
+    }
+
+    impl GitHubRequest for ChangeReviewRequests {
+        type Response = PullRequest;
+
+        fn method(&self) -> Method {
+            self.method.clone()
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = pull_endpoint(config, &self.owner, &self.repo, self.number);
+            endpoint.push_str("/requested_reviewers");
+            parse_url(&endpoint)
+        }
+
+        fn body(&self) -> Option<&str> {
+            Some(&self.body)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+use config::Config;
+use evidence::json_ops;
+use git_hub::GitHubRequest;
+use hyper::method::Method;
+use super::{PullRequest, PullRequestRef, ReviewRequest};
+use super::requests::ChangeReviewRequests;
+
+    const PULL_REQUEST_REF: &'static str = "{\"label\":\"octocat:new-topic\",\"ref\":\"new-topic\",\
+                                            \"sha\":\"6dcb09b5b57875f334f61aebed695e2e4193db5e\",\
+                                            \"repo\":{\"full_name\":\"octocat/Hello-World\",\
+                                            \"clone_url\":\"https://github.com/octocat/Hello-World.git\"}}";
+
+    fn pull_request() -> String {
+        format!("{{\"id\":1,\"node_id\":\"MDExOlB1bGxSZXF1ZXN0MQ==\",\"number\":1347,\"state\":\"open\",\
+                 \"title\":\"Amazing new feature\",\
+                 \"url\":\"https://api.github.com/repos/octocat/Hello-World/pulls/1347\",\
+                 \"html_url\":\"https://github.com/octocat/Hello-World/pull/1347\",\
+                 \"user\":{{\"login\":\"octocat\",\"id\":1}},\"head\":{0},\"base\":{0},\
+                 \"body\":\"Please pull these awesome changes\",\"draft\":false,\"merged\":false,\
+                 \"mergeable\":true,\"mergeable_state\":\"clean\",\"merged_by\":{{\"login\":\"octocat\",\"id\":1}},\
+                 \"requested_reviewers\":[{{\"login\":\"other_user\",\"id\":2}}],\
+                 \"requested_teams\":[{{\"name\":\"Justice League\",\"slug\":\"justice-league\"}}],\
+                 \"comments\":10,\"review_comments\":0,\"commits\":3,\"additions\":100,\"deletions\":3,\
+                 \"changed_files\":5,\"created_at\":\"2011-01-26T19:01:12Z\",\"updated_at\":\"2011-01-26T19:01:12Z\",\
+                 \"closed_at\":null,\"merged_at\":null}}", PULL_REQUEST_REF)
+    }
+
+    fn build_test_config() -> Config {
+        Config {
+            username: "octocat".to_owned(),
+            access_token: "abcdefg1234567".to_owned(),
+            api_url: None,
+            credential_helper: None
+        }
+    }
+
+    fn change_review_requests(method: Method, review_request: &ReviewRequest) -> ChangeReviewRequests {
+        ChangeReviewRequests {
+            owner: "octocat".to_owned(),
+            repo: "Hello-World".to_owned(),
+            number: 1347,
+            method: method,
+            body: json_ops::to_json(review_request, "").unwrap()
+        }
+    }
+
+    #[test]
+    fn test_request_reviewers() -> () {
+        let review_request = ReviewRequest {
+            reviewers: vec!["octocat".to_owned(), "hubot".to_owned()],
+            team_reviewers: vec!["justice-league".to_owned()]
+        };
+        let request = change_review_requests(Method::Post, &review_request);
+        assert_eq!(request.method(), Method::Post);
+        assert_eq!(request.url(&build_test_config()).unwrap().as_str(),
+                   "https://api.github.com/repos/octocat/Hello-World/pulls/1347/requested_reviewers");
+        assert_eq!(request.body(),
+                   Some("{\"reviewers\":[\"octocat\",\"hubot\"],\"team_reviewers\":[\"justice-league\"]}"));
+    }
+
+    #[test]
+    fn test_remove_requested_reviewers() -> () {
+        let review_request = ReviewRequest { reviewers: vec!["hubot".to_owned()], team_reviewers: vec![] };
+        let request = change_review_requests(Method::Delete, &review_request);
+        assert_eq!(request.method(), Method::Delete);
+        assert_eq!(request.url(&build_test_config()).unwrap().as_str(),
+                   "https://api.github.com/repos/octocat/Hello-World/pulls/1347/requested_reviewers");
+        assert_eq!(request.body(), Some("{\"reviewers\":[\"hubot\"]}"));
+    }
+
+    #[test]
+    fn test_remove_requested_teams_only() -> () {
+        let review_request = ReviewRequest { reviewers: vec![], team_reviewers: vec!["justice-league".to_owned()] };
+        let request = change_review_requests(Method::Delete, &review_request);
+        assert_eq!(request.body(), Some("{\"reviewers\":[],\"team_reviewers\":[\"justice-league\"]}"));
+    }
+
+    quickcheck! {
+        fn prop_pull_request_optional_fields(choices: Vec<u8>, text: String) -> bool {
+            let nullable = ["user", "body", "draft", "mergeable", "mergeable_state", "merged_by", "closed_at",
+                            "merged_at"];
+            let omittable = ["node_id", "merged", "requested_reviewers", "requested_teams", "comments",
+                             "review_comments", "commits", "additions", "deletions", "changed_files"];
+            let json = json_ops::vary_fields(&pull_request(), &nullable, &omittable, &choices, &text);
+            json_ops::from_str::<PullRequest>(&json, "").is_ok()
+        }
+
+        // The repository of a head branch is null once its fork is deleted
+        fn prop_pull_request_ref_optional_fields(choices: Vec<u8>, text: String) -> bool {
+            let json = json_ops::vary_fields(PULL_REQUEST_REF, &["repo"], &[], &choices, &text);
+            json_ops::from_str::<PullRequestRef>(&json, "").is_ok()
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use error::{GhError, GhResult};
+
+use git_hub::{header_value, Client, GitHubResponse};
+
+use hyper::status::StatusCode;
+
+use std::collections::BTreeMap;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// The rate limit status of every API resource. See
+// https://developer.github.com/v3/rate_limit/
+#[derive(Deserialize, Serialize)]
+pub struct RateLimit {
+    pub resources: BTreeMap<String, RateLimitResource>
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct RateLimitResource {
+    pub limit: u64,
+    pub remaining: u64,
+    pub reset: u64,
+    #[serde(default)]
+    pub used: Option<u64>
+}
+
+// How long to wait before retrying a rate limited request
+#[derive(Debug, PartialEq)]
+pub enum RateLimitWait {
+    // From Retry-After, or until X-RateLimit-Reset
+    Seconds(u64),
+    // A secondary limit GitHub gave no wait for, which is backed off from
+    Unknown
+}
+
+pub fn get_rate_limit(client: &Client) -> GhResult<RateLimit> {
+    client.send(requests::GetRateLimit)
+}
+
+// Sends a request through `send`, turning a rate limited response into a
+// GhError::RateLimited. When `wait_on_rate_limit` is set, sleeps
+// until the primary limit resets, or backs off on secondary limits, and
+// retries up to MAX_RETRIES times instead.
+pub fn with_rate_limit<F>(wait_on_rate_limit: bool, send: F) -> GhResult<GitHubResponse>
+    where F: Fn() -> GhResult<GitHubResponse> {
+    let mut attempt = 0;
+    loop {
+        let response = send()?;
+        let wait = match rate_limit_wait(&response, now()) {
+            Some(wait) => wait,
+            None       => return Ok(response),
+        };
+        if !wait_on_rate_limit || attempt >= MAX_RETRIES {
+            return Err(rate_limit_error(&response, &wait));
+        }
+        // secondary limits without a Retry-After back off exponentially
+        let wait = match wait {
+            RateLimitWait::Seconds(wait) => wait,
+            RateLimitWait::Unknown       => BACKOFF_SECONDS << attempt
+        };
+        eprintln!("gh: GitHub rate limit reached, retrying in {} seconds", wait);
+        thread::sleep(Duration::from_secs(wait));
+        attempt += 1;
+    }
+}
+
+// Returns how long to wait before retrying if `response` was rate limited,
+// or None if it wasn't. A primary limit that has already reset is retried
+// after CLOCK_SKEW_SECONDS, in case our clock is ahead of GitHub's.
+//   primary limits:   403 / 429 with X-RateLimit-Remaining: 0 and X-RateLimit-Reset
+//   secondary limits: 403 / 429 with Retry-After, or a "secondary rate limit" message
+pub fn rate_limit_wait(response: &GitHubResponse, now: u64) -> Option<RateLimitWait> {
+    if response.status != StatusCode::Forbidden && response.status != StatusCode::TooManyRequests {
+        return None;
+    }
+    let headers = &response.headers;
+    if let Some(retry_after) = header_value(headers, "Retry-After").and_then(|x| x.parse::<u64>().ok()) {
+        return Some(RateLimitWait::Seconds(retry_after));
+    }
+    if header_value(headers, "X-RateLimit-Remaining") == Some("0".to_owned()) {
+        let reset = header_value(headers, "X-RateLimit-Reset").and_then(|x| x.parse::<u64>().ok());
+        return Some(match reset {
+            Some(reset) if reset > now => RateLimitWait::Seconds(reset - now),
+            Some(_)                    => RateLimitWait::Seconds(CLOCK_SKEW_SECONDS),
+            None                       => RateLimitWait::Unknown
+        });
+    }
+    let is_secondary = match response.body {
+        Some(ref body) => body.to_lowercase().contains("secondary rate limit") ||
+                          body.to_lowercase().contains("abuse detection"),
+        None           => false
+    };
+    if is_secondary { Some(RateLimitWait::Unknown) } else { None }
+}
+
+fn rate_limit_error(response: &GitHubResponse, wait: &RateLimitWait) -> GhError {
+    match *wait {
+        RateLimitWait::Seconds(wait) => {
+            let msg = format!("{} GitHub rate limit exceeded. It resets in {} seconds, or pass --wait-for-rate-limit.",
+                              response.status, wait);
+            GhError::RateLimited(wait, msg)
+        },
+        RateLimitWait::Unknown       => {
+            let msg = format!("{} GitHub rate limit exceeded. Try again later, or pass --wait-for-rate-limit.",
+                              response.status);
+            GhError::RateLimited(0, msg)
+        }
+    }
+}
+
+// Seconds since the Unix epoch
+pub fn now() -> u64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs(),
+        Err(_)       => 0
+    }
+}
+
+const MAX_RETRIES: u32 = 3;
+const BACKOFF_SECONDS: u64 = 60;
+const CLOCK_SKEW_SECONDS: u64 = 1;
+
+mod requests {
+use config::Config;
+use error::GhResult;
+use git_hub::{parse_url, GitHubRequest};
+use git_hub::rate_limit::RateLimit;
+use hyper::Url;
+
+    // Gets the rate limit status for the authenticated user
+    // GET /rate_limit
+    // See https://developer.github.com/v3/rate_limit/
+    // for more information
+    pub struct GetRateLimit;
+
+    impl GitHubRequest for GetRateLimit {
+        type Response = RateLimit;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = String::with_capacity(50);
+            endpoint.push_str(config.api_url());
+            endpoint.push_str("/rate_limit");
+            parse_url(&endpoint)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+use git_hub::GitHubResponse;
+use hyper::header::Headers;
+use hyper::status::StatusCode;
+use super::{rate_limit_wait, RateLimitWait};
+
+    fn build_response(status: StatusCode, headers: Vec<(&str, &str)>, body: &str) -> GitHubResponse {
+        let mut hs = Headers::new();
+        for (name, value) in headers {
+            hs.set_raw(name.to_owned(), vec![value.as_bytes().to_vec()]);
+        }
+        GitHubResponse {
+            status: status,
+            headers: hs,
+            body: Some(body.to_owned())
+        }
+    }
+
+    #[test]
+    fn test_rate_limit_wait_primary() -> () {
+        let response = build_response(StatusCode::Forbidden,
+                                      vec![("X-RateLimit-Remaining", "0"), ("X-RateLimit-Reset", "1100")],
+                                      "{\"message\":\"API rate limit exceeded\"}");
+        assert_eq!(rate_limit_wait(&response, 1000), Some(RateLimitWait::Seconds(100)));
+    }
+
+    #[test]
+    fn test_rate_limit_wait_primary_already_reset() -> () {
+        let response = build_response(StatusCode::Forbidden,
+                                      vec![("X-RateLimit-Remaining", "0"), ("X-RateLimit-Reset", "900")],
+                                      "{\"message\":\"API rate limit exceeded\"}");
+        assert_eq!(rate_limit_wait(&response, 1000), Some(RateLimitWait::Seconds(1)));
+    }
+
+    #[test]
+    fn test_rate_limit_wait_secondary_retry_after() -> () {
+        let response = build_response(StatusCode::TooManyRequests, vec![("Retry-After", "30")], "");
+        assert_eq!(rate_limit_wait(&response, 1000), Some(RateLimitWait::Seconds(30)));
+    }
+
+    #[test]
+    fn test_rate_limit_wait_secondary_message() -> () {
+        let response = build_response(StatusCode::Forbidden, vec![],
+                                      "{\"message\":\"You have exceeded a secondary rate limit.\"}");
+        assert_eq!(rate_limit_wait(&response, 1000), Some(RateLimitWait::Unknown));
+    }
+
+    #[test]
+    fn test_rate_limit_wait_scope_forbidden() -> () {
+        let response = build_response(StatusCode::Forbidden,
+                                      vec![("X-RateLimit-Remaining", "4999")],
+                                      "{\"message\":\"Resource not accessible by integration\"}");
+        assert_eq!(rate_limit_wait(&response, 1000), None);
+    }
+
+    #[test]
+    fn test_rate_limit_wait_ok() -> () {
+        let response = build_response(StatusCode::Ok, vec![("X-RateLimit-Remaining", "0")], "[]");
+        assert_eq!(rate_limit_wait(&response, 1000), None);
+    }
+}
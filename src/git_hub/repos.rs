@@ -0,0 +1,480 @@
+use error::GhResult;
+
+use evidence::json_ops;
+use git_hub::Client;
+use git_hub::pagination;
+use git_hub::pagination::Pagination;
+
+// GitHub Repository. See https://developer.github.com/v3/repos/
+#[derive(Deserialize, Serialize)]
+pub struct Repository {
+    pub id: u64,
+    pub name: String,
+    pub full_name: String,
+    pub owner: RepositoryOwner,
+    pub private: bool,
+    #[serde(default)]
+    pub visibility: Option<String>,
+    pub description: Option<String>,
+    pub fork: bool,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub is_template: bool,
+    pub url: String,
+    pub html_url: String,
+    pub clone_url: String,
+    pub ssh_url: String,
+    pub homepage: Option<String>,
+    pub language: Option<String>,
+    pub default_branch: String,
+    pub stargazers_count: u32,
+    pub forks_count: u32,
+    pub open_issues_count: u32,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    #[serde(default)]
+    pub license: Option<RepositoryLicense>,
+    // Only set when getting a single forked repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<RepositoryParent>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub pushed_at: Option<String>
+}
+
+// The GitHub User or Organization owning a Repository
+#[derive(Deserialize, Serialize)]
+pub struct RepositoryOwner {
+    pub login: String,
+    pub id: u64,
+    #[serde(rename = "type")]
+    pub owner_type: String
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct RepositoryLicense {
+    pub key: String,
+    pub name: String,
+    #[serde(default)]
+    pub spdx_id: Option<String>
+}
+
+// The Repository a fork was made from
+#[derive(Deserialize, Serialize)]
+pub struct RepositoryParent {
+    pub full_name: String,
+    pub html_url: String
+}
+
+// The body of a request to create a new Repository.
+// See https://developer.github.com/v3/repos/#create
+#[derive(Default, Deserialize, Serialize)]
+pub struct NewRepository {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_init: Option<bool>
+}
+
+// The body of a request to create a new Repository from a template.
+// See https://developer.github.com/v3/repos/#create-repository-using-a-repository-template
+#[derive(Deserialize, Serialize)]
+pub struct NewRepositoryFromTemplate {
+    pub owner: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_all_branches: Option<bool>
+}
+
+// The body of a request to fork a Repository.
+// See https://developer.github.com/v3/repos/forks/#create-a-fork
+#[derive(Default, Deserialize, Serialize)]
+pub struct NewFork {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_branch_only: Option<bool>
+}
+
+// The body of a request to edit a Repository. Only the fields given are changed.
+// See https://developer.github.com/v3/repos/#edit
+#[derive(Default, Deserialize, Serialize)]
+pub struct EditRepository {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>
+}
+
+// Who owns the Repositories to list.
+pub enum RepositoryOwnerKind {
+    AuthedUser,
+    User(String),
+    Org(String)
+}
+
+// How to filter and order a list of Repositories.
+//   repo_type  - all, owner, public, private, member, forks or sources; which
+//                are accepted depends on who owns the Repositories
+//   sort       - created, updated, pushed or full_name
+//   visibility - all, public or private, only for the authenticated user
+#[derive(Clone, Default)]
+pub struct RepositoryFilters {
+    pub repo_type: Option<String>,
+    pub sort: Option<String>,
+    pub visibility: Option<String>
+}
+
+pub fn list_repos(owner: &RepositoryOwnerKind,
+                  filters: &RepositoryFilters,
+                  client: &Client,
+                  pagination: &Pagination) -> GhResult<Vec<Repository>> {
+    let path = match *owner {
+        RepositoryOwnerKind::AuthedUser    => "/user/repos".to_owned(),
+        RepositoryOwnerKind::User(ref user) => format!("/users/{}/repos", user),
+        RepositoryOwnerKind::Org(ref org)   => format!("/orgs/{}/repos", org)
+    };
+    let request = requests::ListRepositories {
+        path: path,
+        filters: filters.clone(),
+        pagination: pagination.clone()
+    };
+    pagination::send_all_pages(client, request, pagination)
+}
+
+pub fn get_repo(owner: &str, repo: &str, client: &Client) -> GhResult<Repository> {
+    client.send(requests::GetRepository {
+        owner: owner.to_owned(),
+        repo: repo.to_owned()
+    })
+}
+
+// Creates a Repository for the authenticated user, or in `org` if given.
+pub fn create_repo(org: Option<&str>, repository: &NewRepository, client: &Client) -> GhResult<Repository> {
+    let path = match org {
+        None      => "/user/repos".to_owned(),
+        Some(org) => format!("/orgs/{}/repos", org)
+    };
+    client.send(requests::CreateRepository {
+        path: path,
+        body: json_ops::to_json(repository, SERIALIZE_NEW_REPOSITORY)?
+    })
+}
+
+pub fn create_repo_from_template(template_owner: &str,
+                                 template_repo: &str,
+                                 repository: &NewRepositoryFromTemplate,
+                                 client: &Client) -> GhResult<Repository> {
+    client.send(requests::CreateRepository {
+        path: format!("/repos/{}/{}/generate", template_owner, template_repo),
+        body: json_ops::to_json(repository, SERIALIZE_NEW_REPOSITORY)?
+    })
+}
+
+// GitHub creates forks asynchronously, so the returned Repository may not
+// be ready to clone for a few moments.
+pub fn fork_repo(owner: &str, repo: &str, fork: &NewFork, client: &Client) -> GhResult<Repository> {
+    client.send(requests::CreateRepository {
+        path: format!("/repos/{}/{}/forks", owner, repo),
+        body: json_ops::to_json(fork, SERIALIZE_NEW_FORK)?
+    })
+}
+
+pub fn edit_repo(owner: &str, repo: &str, edit: &EditRepository, client: &Client) -> GhResult<Repository> {
+    client.send(requests::EditRepository {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        body: json_ops::to_json(edit, SERIALIZE_EDIT_REPOSITORY)?
+    })
+}
+
+pub fn delete_repo(owner: &str, repo: &str, client: &Client) -> GhResult<()> {
+    client.send(requests::DeleteRepository {
+        owner: owner.to_owned(),
+        repo: repo.to_owned()
+    })
+}
+
+// Deletes a branch, e.g. the head branch of a merged Pull Request
+pub fn delete_branch(owner: &str, repo: &str, branch: &str, client: &Client) -> GhResult<()> {
+    client.send(requests::DeleteBranch {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        branch: branch.to_owned()
+    })
+}
+
+const SERIALIZE_NEW_REPOSITORY: &'static str = "Error serializing new GitHub Repository JSON.";
+const SERIALIZE_NEW_FORK: &'static str = "Error serializing GitHub fork JSON.";
+const SERIALIZE_EDIT_REPOSITORY: &'static str = "Error serializing GitHub Repository edit JSON.";
+
+mod requests {
+use config::Config;
+use error::{GhError, GhResult};
+use git_hub::{parse_url, GitHubRequest, GitHubResponse};
+use git_hub::pagination::Pagination;
+use git_hub::repos::{Repository, RepositoryFilters};
+use hyper::Url;
+use hyper::method::Method;
+
+    fn repo_endpoint(config: &Config, owner: &str, repo: &str) -> String {
+        let mut endpoint = String::with_capacity(60);
+        endpoint.push_str(config.api_url());
+        endpoint.push_str("/repos/");
+        endpoint.push_str(owner);
+        endpoint.push_str("/");
+        endpoint.push_str(repo);
+        endpoint
+    }
+
+    // Lists the Repositories of the authenticated user, a user, or an organization
+    // GET /user/repos
+    // GET /users/:username/repos
+    // GET /orgs/:org/repos
+    // See https://developer.github.com/v3/repos/#list-your-repositories
+    // for more information
+    pub struct ListRepositories {
+        pub path: String,
+        pub filters: RepositoryFilters,
+        pub pagination: Pagination
+    }
+
+    impl GitHubRequest for ListRepositories {
+        type Response = Vec<Repository>;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = String::with_capacity(50);
+            endpoint.push_str(config.api_url());
+            endpoint.push_str(&self.path);
+            let mut url = parse_url(&endpoint)?;
+            let query = vec![("type", self.filters.repo_type.as_ref()),
+                             ("sort", self.filters.sort.as_ref()),
+                             ("visibility", self.filters.visibility.as_ref())];
+            for (name, value) in query {
+                if let Some(value) = value {
+                    url.query_pairs_mut().append_pair(name, value);
+                }
+            }
+            self.pagination.append_query(&mut url);
+            Ok(url)
+        }
+    }
+
+    // Gets a single Repository
+    // GET /repos/:owner/:repo
+    // See https://developer.github.com/v3/repos/#get
+    // for more information
+    pub struct GetRepository {
+        pub owner: String,
+        pub repo: String
+    }
+
+    impl GitHubRequest for GetRepository {
+        type Response = Repository;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            parse_url(&repo_endpoint(config, &self.owner, &self.repo))
+        }
+    }
+
+    // Creates a Repository, from scratch, from a template, or as a fork
+    // POST /user/repos
+    // POST /orgs/:org/repos
+    // POST /repos/:template_owner/:template_repo/generate
+    // POST /repos/:owner/:repo/forks
+    // See https://developer.github.com/v3/repos/#create
+    // for more information
+    pub struct CreateRepository {
+        pub path: String,
+        pub body: String
+    }
+
+    impl GitHubRequest for CreateRepository {
+        type Response = Repository;
+
+        fn method(&self) -> Method {
+            Method::Post
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = String::with_capacity(50);
+            endpoint.push_str(config.api_url());
+            endpoint.push_str(&self.path);
+            parse_url(&endpoint)
+        }
+
+        fn body(&self) -> Option<&str> {
+            Some(&self.body)
+        }
+    }
+
+    // Edits a Repository, e.g. to archive it
+    // PATCH /repos/:owner/:repo
+    // See https://developer.github.com/v3/repos/#edit
+    // for more information
+    pub struct EditRepository {
+        pub owner: String,
+        pub repo: String,
+        pub body: String
+    }
+
+    impl GitHubRequest for EditRepository {
+        type Response = Repository;
+
+        fn method(&self) -> Method {
+            Method::Patch
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            parse_url(&repo_endpoint(config, &self.owner, &self.repo))
+        }
+
+        fn body(&self) -> Option<&str> {
+            Some(&self.body)
+        }
+    }
+
+    // Deletes a Repository. Requires the delete_repo scope.
+    // DELETE /repos/:owner/:repo
+    // See https://developer.github.com/v3/repos/#delete-a-repository
+    // for more information
+    pub struct DeleteRepository {
+        pub owner: String,
+        pub repo: String
+    }
+
+    impl GitHubRequest for DeleteRepository {
+        type Response = ();
+
+        fn method(&self) -> Method {
+            Method::Delete
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            parse_url(&repo_endpoint(config, &self.owner, &self.repo))
+        }
+
+        // 204 No Content
+        fn decode(&self, _: &GitHubResponse) -> GhResult<()> {
+            Ok(())
+        }
+    }
+
+    // Deletes a branch
+    // DELETE /repos/:owner/:repo/git/refs/heads/:branch
+    // See https://developer.github.com/v3/git/refs/#delete-a-reference
+    // for more information
+    pub struct DeleteBranch {
+        pub owner: String,
+        pub repo: String,
+        pub branch: String
+    }
+
+    impl GitHubRequest for DeleteBranch {
+        type Response = ();
+
+        fn method(&self) -> Method {
+            Method::Delete
+        }
+
+        // Branch names may hold `#` or `?`, so each part between the slashes
+        // of the name is pushed as an escaped path segment
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = repo_endpoint(config, &self.owner, &self.repo);
+            endpoint.push_str("/git/refs/heads");
+            let mut url = parse_url(&endpoint)?;
+            url.path_segments_mut()
+               .map_err(|_| GhError::Usage(format!("hyper::Url could not add a branch to URL {}", endpoint)))?
+               .extend(self.branch.split('/'));
+            Ok(url)
+        }
+
+        // 204 No Content
+        fn decode(&self, _: &GitHubResponse) -> GhResult<()> {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+use config::Config;
+use evidence::json_ops;
+use git_hub::GitHubRequest;
+use git_hub::pagination::Pagination;
+use super::{Repository, RepositoryFilters};
+use super::requests::{DeleteBranch, ListRepositories};
+
+    const REPOSITORY: &'static str = "{\"id\":1296269,\"name\":\"Hello-World\",\"full_name\":\"octocat/Hello-World\",\
+                                      \"owner\":{\"login\":\"octocat\",\"id\":1,\"type\":\"User\"},\"private\":false,\
+                                      \"visibility\":\"public\",\"description\":\"This your first repo!\",\"fork\":true,\
+                                      \"archived\":false,\"is_template\":false,\
+                                      \"url\":\"https://api.github.com/repos/octocat/Hello-World\",\
+                                      \"html_url\":\"https://github.com/octocat/Hello-World\",\
+                                      \"clone_url\":\"https://github.com/octocat/Hello-World.git\",\
+                                      \"ssh_url\":\"git@github.com:octocat/Hello-World.git\",\
+                                      \"homepage\":\"https://github.com\",\"language\":null,\"default_branch\":\"master\",\
+                                      \"stargazers_count\":80,\"forks_count\":9,\"open_issues_count\":0,\
+                                      \"topics\":[\"octocat\",\"api\"],\
+                                      \"license\":{\"key\":\"mit\",\"name\":\"MIT License\",\"spdx_id\":\"MIT\"},\
+                                      \"parent\":{\"full_name\":\"octocat/Hello\",\"html_url\":\"https://github.com/octocat/Hello\"},\
+                                      \"created_at\":\"2011-01-26T19:01:12Z\",\"updated_at\":\"2011-01-26T19:14:43Z\",\
+                                      \"pushed_at\":\"2011-01-26T19:06:43Z\"}";
+
+    quickcheck! {
+        fn prop_repository_optional_fields(choices: Vec<u8>, text: String) -> bool {
+            let nullable = ["visibility", "description", "homepage", "language", "license", "parent", "pushed_at"];
+            let omittable = ["archived", "is_template", "topics"];
+            let json = json_ops::vary_fields(REPOSITORY, &nullable, &omittable, &choices, &text);
+            json_ops::from_str::<Repository>(&json, "").is_ok()
+        }
+    }
+
+    #[test]
+    fn test_list_repositories_url() -> () {
+        let config = Config {
+            username: "octocat".to_owned(),
+            access_token: "abcdefg1234567".to_owned(),
+            api_url: None,
+            credential_helper: None
+        };
+        let request = ListRepositories {
+            path: "/orgs/github/repos".to_owned(),
+            filters: RepositoryFilters {
+                repo_type: Some("sources".to_owned()),
+                sort: Some("pushed".to_owned()),
+                visibility: None
+            },
+            pagination: Pagination::default()
+        };
+        assert_eq!(request.url(&config).unwrap().as_str(),
+                   "https://api.github.com/orgs/github/repos?type=sources&sort=pushed");
+    }
+
+    #[test]
+    fn test_delete_branch_url() -> () {
+        let config = Config {
+            username: "octocat".to_owned(),
+            access_token: "abcdefg1234567".to_owned(),
+            api_url: None,
+            credential_helper: None
+        };
+        let request = DeleteBranch {
+            owner: "octocat".to_owned(),
+            repo: "Hello-World".to_owned(),
+            branch: "feature/fix-#12?".to_owned()
+        };
+        assert_eq!(request.url(&config).unwrap().as_str(),
+                   "https://api.github.com/repos/octocat/Hello-World/git/refs/heads/feature/fix-%2312%3F");
+    }
+}
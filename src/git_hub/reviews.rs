@@ -0,0 +1,274 @@
+use error::GhResult;
+
+use evidence::json_ops;
+use git_hub::Client;
+use git_hub::pagination;
+use git_hub::pagination::Pagination;
+use git_hub::pulls::PullRequestUser;
+
+// A review of a Pull Request.
+// See https://developer.github.com/v3/pulls/reviews/
+#[derive(Deserialize, Serialize)]
+pub struct Review {
+    pub id: u64,
+    // null once the reviewer's account has been deleted
+    pub user: Option<PullRequestUser>,
+    pub body: Option<String>,
+    // APPROVED, CHANGES_REQUESTED, COMMENTED, DISMISSED or PENDING
+    pub state: String,
+    pub html_url: String,
+    pub commit_id: Option<String>,
+    // null while the review is PENDING
+    pub submitted_at: Option<String>
+}
+
+// A comment on a line of a Pull Request's diff, made as part of a review.
+// See https://developer.github.com/v3/pulls/comments/
+#[derive(Deserialize, Serialize)]
+pub struct ReviewComment {
+    pub id: u64,
+    pub pull_request_review_id: Option<u64>,
+    pub path: String,
+    // null when the line is no longer part of the diff
+    pub line: Option<u32>,
+    pub original_line: Option<u32>,
+    pub diff_hunk: String,
+    pub user: Option<PullRequestUser>,
+    pub body: String,
+    pub in_reply_to_id: Option<u64>,
+    pub html_url: String,
+    pub created_at: String,
+    pub updated_at: String
+}
+
+// The body of a request to review a Pull Request.
+//   event - APPROVE, REQUEST_CHANGES or COMMENT
+//   body  - required unless approving
+// See https://developer.github.com/v3/pulls/reviews/#create-a-pull-request-review
+#[derive(Deserialize, Serialize)]
+pub struct NewReview {
+    pub event: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>
+}
+
+pub fn list_reviews(owner: &str,
+                    repo: &str,
+                    number: u32,
+                    client: &Client,
+                    pagination: &Pagination) -> GhResult<Vec<Review>> {
+    let request = requests::ListReviews {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        number: number,
+        pagination: pagination.clone()
+    };
+    pagination::send_all_pages(client, request, pagination)
+}
+
+pub fn create_review(owner: &str,
+                     repo: &str,
+                     number: u32,
+                     review: &NewReview,
+                     client: &Client) -> GhResult<Review> {
+    client.send(requests::CreateReview {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        number: number,
+        body: json_ops::to_json(review, SERIALIZE_NEW_REVIEW)?
+    })
+}
+
+pub fn list_review_comments(owner: &str,
+                            repo: &str,
+                            number: u32,
+                            client: &Client,
+                            pagination: &Pagination) -> GhResult<Vec<ReviewComment>> {
+    let request = requests::ListReviewComments {
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        number: number,
+        pagination: pagination.clone()
+    };
+    pagination::send_all_pages(client, request, pagination)
+}
+
+const SERIALIZE_NEW_REVIEW: &'static str = "Error serializing new GitHub Pull Request Review JSON.";
+
+mod requests {
+use config::Config;
+use error::GhResult;
+use git_hub::{parse_url, GitHubRequest};
+use git_hub::pagination::Pagination;
+use git_hub::pulls::requests::pull_endpoint;
+use git_hub::reviews::{Review, ReviewComment};
+use hyper::Url;
+use hyper::method::Method;
+
+    // Lists the reviews of a Pull Request
+    // GET /repos/:owner/:repo/pulls/:number/reviews
+    // See https://developer.github.com/v3/pulls/reviews/#list-reviews-on-a-pull-request
+    // for more information
+    pub struct ListReviews {
+        pub owner: String,
+        pub repo: String,
+        pub number: u32,
+        pub pagination: Pagination
+    }
+
+    impl GitHubRequest for ListReviews {
+        type Response = Vec<Review>;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = pull_endpoint(config, &self.owner, &self.repo, self.number);
+            endpoint.push_str("/reviews");
+            let mut url = parse_url(&endpoint)?;
+            self.pagination.append_query(&mut url);
+            Ok(url)
+        }
+    }
+
+    // Reviews a Pull Request
+    // POST /repos/:owner/:repo/pulls/:number/reviews
+    // See https://developer.github.com/v3/pulls/reviews/#create-a-pull-request-review
+    // for more information
+    pub struct CreateReview {
+        pub owner: String,
+        pub repo: String,
+        pub number: u32,
+        pub body: String
+    }
+
+    impl GitHubRequest for CreateReview {
+        type Response = Review;
+
+        fn method(&self) -> Method {
+            Method::Post
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = pull_endpoint(config, &self.owner, &self.repo, self.number);
+            endpoint.push_str("/reviews");
+            parse_url(&endpoint)
+        }
+
+        fn body(&self) -> Option<&str> {
+            Some(&self.body)
+        }
+    }
+
+    // Lists the review comments on a Pull Request's diff
+    // GET /repos/:owner/:repo/pulls/:number/comments
+    // See https://developer.github.com/v3/pulls/comments/#list-comments-on-a-pull-request
+    // for more information
+    pub struct ListReviewComments {
+        pub owner: String,
+        pub repo: String,
+        pub number: u32,
+        pub pagination: Pagination
+    }
+
+    impl GitHubRequest for ListReviewComments {
+        type Response = Vec<ReviewComment>;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = pull_endpoint(config, &self.owner, &self.repo, self.number);
+            endpoint.push_str("/comments");
+            let mut url = parse_url(&endpoint)?;
+            self.pagination.append_query(&mut url);
+            Ok(url)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+use config::Config;
+use evidence::json_ops;
+use git_hub::GitHubRequest;
+use git_hub::pagination::Pagination;
+use hyper::method::Method;
+use super::{NewReview, Review, ReviewComment};
+use super::requests::{CreateReview, ListReviewComments, ListReviews};
+
+    const REVIEW: &'static str = "{\"id\":80,\"user\":{\"login\":\"octocat\",\"id\":1},\
+                                  \"body\":\"Here is the body for the review.\",\"state\":\"APPROVED\",\
+                                  \"html_url\":\"https://github.com/octocat/Hello-World/pull/12#pullrequestreview-80\",\
+                                  \"commit_id\":\"ecdd80bb57125d7ba9641ffaa4d7d2c19d3f3091\",\
+                                  \"submitted_at\":\"2019-11-17T17:43:43Z\"}";
+
+    const REVIEW_COMMENT: &'static str = "{\"id\":10,\"pull_request_review_id\":42,\"path\":\"file1.txt\",\"line\":2,\
+                                          \"original_line\":2,\"diff_hunk\":\"@@ -16,33 +16,40 @@\",\
+                                          \"user\":{\"login\":\"octocat\",\"id\":1},\"body\":\"Great stuff!\",\
+                                          \"in_reply_to_id\":8,\
+                                          \"html_url\":\"https://github.com/octocat/Hello-World/pull/1#discussion-diff-1\",\
+                                          \"created_at\":\"2011-04-14T16:00:49Z\",\"updated_at\":\"2011-04-14T16:00:49Z\"}";
+
+    fn build_test_config() -> Config {
+        Config {
+            username: "octocat".to_owned(),
+            access_token: "abcdefg1234567".to_owned(),
+            api_url: None,
+            credential_helper: None
+        }
+    }
+
+    #[test]
+    fn test_list_reviews_url() -> () {
+        let request = ListReviews {
+            owner: "octocat".to_owned(),
+            repo: "Hello-World".to_owned(),
+            number: 12,
+            pagination: Pagination { per_page: Some(50), page: Some(2), limit: None }
+        };
+        assert_eq!(request.url(&build_test_config()).unwrap().as_str(),
+                   "https://api.github.com/repos/octocat/Hello-World/pulls/12/reviews?per_page=50&page=2");
+    }
+
+    #[test]
+    fn test_create_review() -> () {
+        let review = NewReview { event: "APPROVE".to_owned(), body: None };
+        let request = CreateReview {
+            owner: "octocat".to_owned(),
+            repo: "Hello-World".to_owned(),
+            number: 12,
+            body: json_ops::to_json(&review, "").unwrap()
+        };
+        assert_eq!(request.method(), Method::Post);
+        assert_eq!(request.url(&build_test_config()).unwrap().as_str(),
+                   "https://api.github.com/repos/octocat/Hello-World/pulls/12/reviews");
+        assert_eq!(request.body(), Some("{\"event\":\"APPROVE\"}"));
+    }
+
+    #[test]
+    fn test_create_review_body() -> () {
+        let review = NewReview { event: "REQUEST_CHANGES".to_owned(), body: Some("Needs tests.".to_owned()) };
+        assert_eq!(json_ops::to_json(&review, "").unwrap(),
+                   "{\"event\":\"REQUEST_CHANGES\",\"body\":\"Needs tests.\"}");
+    }
+
+    #[test]
+    fn test_list_review_comments_url() -> () {
+        let request = ListReviewComments {
+            owner: "octocat".to_owned(),
+            repo: "Hello-World".to_owned(),
+            number: 12,
+            pagination: Pagination::default()
+        };
+        assert_eq!(request.url(&build_test_config()).unwrap().as_str(),
+                   "https://api.github.com/repos/octocat/Hello-World/pulls/12/comments");
+    }
+
+    quickcheck! {
+        fn prop_review_optional_fields(choices: Vec<u8>, text: String) -> bool {
+            let json = json_ops::vary_fields(REVIEW, &["user", "body", "commit_id", "submitted_at"], &[], &choices, &text);
+            json_ops::from_str::<Review>(&json, "").is_ok()
+        }
+
+        fn prop_review_comment_optional_fields(choices: Vec<u8>, text: String) -> bool {
+            let nullable = ["pull_request_review_id", "line", "original_line", "user", "in_reply_to_id"];
+            let json = json_ops::vary_fields(REVIEW_COMMENT, &nullable, &[], &choices, &text);
+            json_ops::from_str::<ReviewComment>(&json, "").is_ok()
+        }
+    }
+}
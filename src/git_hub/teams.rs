@@ -0,0 +1,626 @@
+use error::GhResult;
+
+use evidence::json_ops;
+use git_hub::Client;
+use git_hub::orgs::OrgMember;
+use git_hub::pagination;
+use git_hub::pagination::Pagination;
+
+use hyper::method::Method;
+
+// A team of an Organization. Teams are addressed by their slug, the
+// url-safe form of their name.
+// See https://developer.github.com/v3/teams/
+#[derive(Deserialize, Serialize)]
+pub struct Team {
+    pub id: u64,
+    pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+    // secret or closed
+    pub privacy: Option<String>,
+    // the permission new repositories are added with, pull, push or admin
+    pub permission: String,
+    pub url: String,
+    #[serde(default)]
+    pub html_url: Option<String>,
+    // null for a team at the top of the Organization
+    #[serde(default)]
+    pub parent: Option<TeamParent>,
+    // Only set when getting a single team
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub members_count: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repos_count: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct TeamParent {
+    pub id: u64,
+    pub name: String,
+    pub slug: String
+}
+
+// The body of a request to create a team.
+//   maintainers    - logins of the members to make maintainers
+//   repo_names     - OWNER/REPO repositories to give the team access to
+//   privacy        - secret, or closed, visible to every member
+//   parent_team_id - the id of the team to nest it under
+// See https://developer.github.com/v3/teams/#create-team
+#[derive(Default, Deserialize, Serialize)]
+pub struct NewTeam {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub maintainers: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub repo_names: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub privacy: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_team_id: Option<u64>
+}
+
+// The body of a request to edit a team, only the fields given are changed.
+// A parent_team_id of Some(None) moves the team to the top of the
+// Organization.
+// See https://developer.github.com/v3/teams/#edit-team
+#[derive(Default, Deserialize, Serialize)]
+pub struct EditTeam {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub privacy: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_team_id: Option<Option<u64>>
+}
+
+// A user's membership of a team.
+// See https://developer.github.com/v3/teams/members/#get-team-membership
+#[derive(Deserialize, Serialize)]
+pub struct TeamMembership {
+    pub url: String,
+    // maintainer or member
+    pub role: String,
+    // active, or pending until the user accepts the Organization's invitation
+    pub state: String
+}
+
+// Without a role GitHub adds new members as members
+#[derive(Deserialize, Serialize)]
+struct TeamMembershipRole {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    role: Option<String>
+}
+
+// A repository a team has access to, with the team's permissions on it
+#[derive(Deserialize, Serialize)]
+pub struct TeamRepository {
+    pub id: u64,
+    pub full_name: String,
+    pub private: bool,
+    pub html_url: String,
+    pub permissions: TeamRepositoryPermissions
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct TeamRepositoryPermissions {
+    pub admin: bool,
+    #[serde(default)]
+    pub maintain: bool,
+    pub push: bool,
+    #[serde(default)]
+    pub triage: bool,
+    pub pull: bool
+}
+
+// Without a permission GitHub gives the team its default permission
+#[derive(Deserialize, Serialize)]
+struct TeamRepositoryPermission {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permission: Option<String>
+}
+
+pub fn list_teams(org: &str, client: &Client, pagination: &Pagination) -> GhResult<Vec<Team>> {
+    let request = requests::ListTeams {
+        org: org.to_owned(),
+        team_slug: None,
+        pagination: pagination.clone()
+    };
+    pagination::send_all_pages(client, request, pagination)
+}
+
+// The teams nested directly under a team
+pub fn list_child_teams(org: &str,
+                        team_slug: &str,
+                        client: &Client,
+                        pagination: &Pagination) -> GhResult<Vec<Team>> {
+    let request = requests::ListTeams {
+        org: org.to_owned(),
+        team_slug: Some(team_slug.to_owned()),
+        pagination: pagination.clone()
+    };
+    pagination::send_all_pages(client, request, pagination)
+}
+
+pub fn get_team(org: &str, team_slug: &str, client: &Client) -> GhResult<Team> {
+    client.send(requests::Team {
+        org: org.to_owned(),
+        team_slug: Some(team_slug.to_owned()),
+        method: Method::Get,
+        body: None
+    })
+}
+
+pub fn create_team(org: &str, team: &NewTeam, client: &Client) -> GhResult<Team> {
+    client.send(requests::Team {
+        org: org.to_owned(),
+        team_slug: None,
+        method: Method::Post,
+        body: Some(json_ops::to_json(team, SERIALIZE_NEW_TEAM)?)
+    })
+}
+
+pub fn edit_team(org: &str, team_slug: &str, edit: &EditTeam, client: &Client) -> GhResult<Team> {
+    client.send(requests::Team {
+        org: org.to_owned(),
+        team_slug: Some(team_slug.to_owned()),
+        method: Method::Patch,
+        body: Some(json_ops::to_json(edit, SERIALIZE_EDIT_TEAM)?)
+    })
+}
+
+// Deletes a team, and every team nested under it
+pub fn delete_team(org: &str, team_slug: &str, client: &Client) -> GhResult<()> {
+    client.send(requests::DeleteTeamResource {
+        org: org.to_owned(),
+        team_slug: team_slug.to_owned(),
+        path: String::new()
+    })
+}
+
+// Lists the members of a team, and of the teams nested under it, optionally
+// only those with `role`, member or maintainer.
+pub fn list_team_members(org: &str,
+                         team_slug: &str,
+                         role: Option<&str>,
+                         client: &Client,
+                         pagination: &Pagination) -> GhResult<Vec<OrgMember>> {
+    let request = requests::ListTeamMembers {
+        org: org.to_owned(),
+        team_slug: team_slug.to_owned(),
+        role: role.map(|x| x.to_owned()),
+        pagination: pagination.clone()
+    };
+    pagination::send_all_pages(client, request, pagination)
+}
+
+// Gets a user's membership of a team, a 404 when they aren't a member.
+pub fn get_team_membership(org: &str, team_slug: &str, username: &str, client: &Client) -> GhResult<TeamMembership> {
+    client.send(requests::GetTeamMembership {
+        org: org.to_owned(),
+        team_slug: team_slug.to_owned(),
+        username: username.to_owned()
+    })
+}
+
+// Adds a user to a team with `role`, member or maintainer, or changes the
+// role of an existing member. Users outside the Organization are invited to
+// it first.
+pub fn set_team_membership(org: &str,
+                           team_slug: &str,
+                           username: &str,
+                           role: Option<&str>,
+                           client: &Client) -> GhResult<TeamMembership> {
+    let role = TeamMembershipRole {
+        role: role.map(|x| x.to_owned())
+    };
+    client.send(requests::SetTeamMembership {
+        org: org.to_owned(),
+        team_slug: team_slug.to_owned(),
+        username: username.to_owned(),
+        body: json_ops::to_json(&role, SERIALIZE_TEAM_MEMBERSHIP_ROLE)?
+    })
+}
+
+pub fn remove_team_membership(org: &str, team_slug: &str, username: &str, client: &Client) -> GhResult<()> {
+    client.send(requests::DeleteTeamResource {
+        org: org.to_owned(),
+        team_slug: team_slug.to_owned(),
+        path: format!("/memberships/{}", username)
+    })
+}
+
+pub fn list_team_repos(org: &str,
+                       team_slug: &str,
+                       client: &Client,
+                       pagination: &Pagination) -> GhResult<Vec<TeamRepository>> {
+    let request = requests::ListTeamRepositories {
+        org: org.to_owned(),
+        team_slug: team_slug.to_owned(),
+        pagination: pagination.clone()
+    };
+    pagination::send_all_pages(client, request, pagination)
+}
+
+// Gets a repository with the team's permissions on it, a 404 when the team
+// has no access to it.
+pub fn get_team_repo(org: &str, team_slug: &str, owner: &str, repo: &str, client: &Client) -> GhResult<TeamRepository> {
+    client.send(requests::GetTeamRepository {
+        org: org.to_owned(),
+        team_slug: team_slug.to_owned(),
+        owner: owner.to_owned(),
+        repo: repo.to_owned()
+    })
+}
+
+// Gives a team `permission` on a repository, pull, triage, push, maintain or
+// admin, or changes the permission it already has.
+pub fn add_team_repo(org: &str,
+                     team_slug: &str,
+                     owner: &str,
+                     repo: &str,
+                     permission: Option<&str>,
+                     client: &Client) -> GhResult<()> {
+    let permission = TeamRepositoryPermission {
+        permission: permission.map(|x| x.to_owned())
+    };
+    client.send(requests::AddTeamRepository {
+        org: org.to_owned(),
+        team_slug: team_slug.to_owned(),
+        owner: owner.to_owned(),
+        repo: repo.to_owned(),
+        body: json_ops::to_json(&permission, SERIALIZE_TEAM_REPOSITORY_PERMISSION)?
+    })
+}
+
+pub fn remove_team_repo(org: &str, team_slug: &str, owner: &str, repo: &str, client: &Client) -> GhResult<()> {
+    client.send(requests::DeleteTeamResource {
+        org: org.to_owned(),
+        team_slug: team_slug.to_owned(),
+        path: format!("/repos/{}/{}", owner, repo)
+    })
+}
+
+const SERIALIZE_NEW_TEAM: &'static str = "Error serializing new GitHub Team JSON.";
+const SERIALIZE_EDIT_TEAM: &'static str = "Error serializing GitHub Team edit JSON.";
+const SERIALIZE_TEAM_MEMBERSHIP_ROLE: &'static str = "Error serializing GitHub Team membership JSON.";
+const SERIALIZE_TEAM_REPOSITORY_PERMISSION: &'static str = "Error serializing GitHub Team repository permission JSON.";
+
+mod requests {
+use config::Config;
+use error::GhResult;
+use git_hub::{parse_url, GitHubRequest, GitHubResponse};
+use git_hub::orgs::OrgMember;
+use git_hub::pagination::Pagination;
+use git_hub::teams;
+use git_hub::teams::{TeamMembership, TeamRepository};
+use hyper::Url;
+use hyper::method::Method;
+
+    fn teams_endpoint(config: &Config, org: &str) -> String {
+        let mut endpoint = String::with_capacity(80);
+        endpoint.push_str(config.api_url());
+        endpoint.push_str("/orgs/");
+        endpoint.push_str(org);
+        endpoint.push_str("/teams");
+        endpoint
+    }
+
+    fn team_endpoint(config: &Config, org: &str, team_slug: &str) -> String {
+        let mut endpoint = teams_endpoint(config, org);
+        endpoint.push_str("/");
+        endpoint.push_str(team_slug);
+        endpoint
+    }
+
+    // Lists an organization's teams, or the child teams of a team
+    // GET /orgs/:org/teams
+    // GET /orgs/:org/teams/:team_slug/teams
+    // See https://developer.github.com/v3/teams/#list-teams
+    // for more information
+    pub struct ListTeams {
+        pub org: String,
+        pub team_slug: Option<String>,
+        pub pagination: Pagination
+    }
+
+    impl GitHubRequest for ListTeams {
+        type Response = Vec<teams::Team>;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let endpoint = match self.team_slug {
+                Some(ref team_slug) => format!("{}/teams", team_endpoint(config, &self.org, team_slug)),
+                None                => teams_endpoint(config, &self.org)
+            };
+            let mut url = parse_url(&endpoint)?;
+            self.pagination.append_query(&mut url);
+            Ok(url)
+        }
+    }
+
+    // Gets, creates or edits a team
+    // GET /orgs/:org/teams/:team_slug
+    // POST /orgs/:org/teams
+    // PATCH /orgs/:org/teams/:team_slug
+    // See https://developer.github.com/v3/teams/#create-team
+    // for more information
+    pub struct Team {
+        pub org: String,
+        pub team_slug: Option<String>,
+        pub method: Method,
+        pub body: Option<String>
+    }
+
+    impl GitHubRequest for Team {
+        type Response = teams::Team;
+
+        fn method(&self) -> Method {
+            self.method.clone()
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            match self.team_slug {
+                Some(ref team_slug) => parse_url(&team_endpoint(config, &self.org, team_slug)),
+                None                => parse_url(&teams_endpoint(config, &self.org))
+            }
+        }
+
+        fn body(&self) -> Option<&str> {
+            self.body.as_ref().map(|x| x.as_str())
+        }
+    }
+
+    // Deletes a team, or removes a member or repository from it
+    // DELETE /orgs/:org/teams/:team_slug
+    // DELETE /orgs/:org/teams/:team_slug/memberships/:username
+    // DELETE /orgs/:org/teams/:team_slug/repos/:owner/:repo
+    // See https://developer.github.com/v3/teams/#delete-team
+    // for more information
+    pub struct DeleteTeamResource {
+        pub org: String,
+        pub team_slug: String,
+        pub path: String
+    }
+
+    impl GitHubRequest for DeleteTeamResource {
+        type Response = ();
+
+        fn method(&self) -> Method {
+            Method::Delete
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = team_endpoint(config, &self.org, &self.team_slug);
+            endpoint.push_str(&self.path);
+            parse_url(&endpoint)
+        }
+
+        // 204 No Content
+        fn decode(&self, _: &GitHubResponse) -> GhResult<()> {
+            Ok(())
+        }
+    }
+
+    // Lists the members of a team
+    // GET /orgs/:org/teams/:team_slug/members
+    // See https://developer.github.com/v3/teams/members/#list-team-members
+    // for more information
+    pub struct ListTeamMembers {
+        pub org: String,
+        pub team_slug: String,
+        pub role: Option<String>,
+        pub pagination: Pagination
+    }
+
+    impl GitHubRequest for ListTeamMembers {
+        type Response = Vec<OrgMember>;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = team_endpoint(config, &self.org, &self.team_slug);
+            endpoint.push_str("/members");
+            let mut url = parse_url(&endpoint)?;
+            if let Some(ref role) = self.role {
+                url.query_pairs_mut().append_pair("role", role);
+            }
+            self.pagination.append_query(&mut url);
+            Ok(url)
+        }
+    }
+
+    // Gets a user's team membership
+    // GET /orgs/:org/teams/:team_slug/memberships/:username
+    // See https://developer.github.com/v3/teams/members/#get-team-membership
+    // for more information
+    pub struct GetTeamMembership {
+        pub org: String,
+        pub team_slug: String,
+        pub username: String
+    }
+
+    impl GitHubRequest for GetTeamMembership {
+        type Response = TeamMembership;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = team_endpoint(config, &self.org, &self.team_slug);
+            endpoint.push_str("/memberships/");
+            endpoint.push_str(&self.username);
+            parse_url(&endpoint)
+        }
+    }
+
+    // Adds or updates a user's team membership
+    // PUT /orgs/:org/teams/:team_slug/memberships/:username
+    // See https://developer.github.com/v3/teams/members/#add-or-update-team-membership
+    // for more information
+    pub struct SetTeamMembership {
+        pub org: String,
+        pub team_slug: String,
+        pub username: String,
+        pub body: String
+    }
+
+    impl GitHubRequest for SetTeamMembership {
+        type Response = TeamMembership;
+
+        fn method(&self) -> Method {
+            Method::Put
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = team_endpoint(config, &self.org, &self.team_slug);
+            endpoint.push_str("/memberships/");
+            endpoint.push_str(&self.username);
+            parse_url(&endpoint)
+        }
+
+        fn body(&self) -> Option<&str> {
+            Some(&self.body)
+        }
+    }
+
+    // Lists the repositories a team has access to
+    // GET /orgs/:org/teams/:team_slug/repos
+    // See https://developer.github.com/v3/teams/#list-team-repos
+    // for more information
+    pub struct ListTeamRepositories {
+        pub org: String,
+        pub team_slug: String,
+        pub pagination: Pagination
+    }
+
+    impl GitHubRequest for ListTeamRepositories {
+        type Response = Vec<TeamRepository>;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = team_endpoint(config, &self.org, &self.team_slug);
+            endpoint.push_str("/repos");
+            let mut url = parse_url(&endpoint)?;
+            self.pagination.append_query(&mut url);
+            Ok(url)
+        }
+    }
+
+    // Gets a repository with the team's permissions on it
+    // GET /orgs/:org/teams/:team_slug/repos/:owner/:repo
+    // See https://developer.github.com/v3/teams/#check-if-a-team-manages-a-repository
+    // for more information
+    pub struct GetTeamRepository {
+        pub org: String,
+        pub team_slug: String,
+        pub owner: String,
+        pub repo: String
+    }
+
+    impl GitHubRequest for GetTeamRepository {
+        type Response = TeamRepository;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = team_endpoint(config, &self.org, &self.team_slug);
+            endpoint.push_str("/repos/");
+            endpoint.push_str(&self.owner);
+            endpoint.push_str("/");
+            endpoint.push_str(&self.repo);
+            parse_url(&endpoint)
+        }
+
+        // Without it GitHub answers 204 No Content, leaving out the permissions
+        fn media_type(&self) -> Option<&str> {
+            Some("application/vnd.github.v3.repository+json")
+        }
+    }
+
+    // Adds or updates a team's permission on a repository
+    // PUT /orgs/:org/teams/:team_slug/repos/:owner/:repo
+    // See https://developer.github.com/v3/teams/#add-or-update-team-repository
+    // for more information
+    pub struct AddTeamRepository {
+        pub org: String,
+        pub team_slug: String,
+        pub owner: String,
+        pub repo: String,
+        pub body: String
+    }
+
+    impl GitHubRequest for AddTeamRepository {
+        type Response = ();
+
+        fn method(&self) -> Method {
+            Method::Put
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = team_endpoint(config, &self.org, &self.team_slug);
+            endpoint.push_str("/repos/");
+            endpoint.push_str(&self.owner);
+            endpoint.push_str("/");
+            endpoint.push_str(&self.repo);
+            parse_url(&endpoint)
+        }
+
+        fn body(&self) -> Option<&str> {
+            Some(&self.body)
+        }
+
+        // 204 No Content
+        fn decode(&self, _: &GitHubResponse) -> GhResult<()> {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+use evidence::json_ops;
+use super::{EditTeam, Team, TeamMembershipRole, TeamRepositoryPermission};
+
+    const TEAM: &'static str = "{\"id\":2,\"name\":\"Bat Family\",\"slug\":\"bat-family\",\
+                                \"description\":\"Gotham's finest\",\"privacy\":\"closed\",\"permission\":\"pull\",\
+                                \"url\":\"https://api.github.com/teams/2\",\
+                                \"html_url\":\"https://github.com/orgs/dc/teams/bat-family\",\
+                                \"parent\":{\"id\":1,\"name\":\"Justice League\",\"slug\":\"justice-league\"},\
+                                \"members_count\":3,\"repos_count\":10,\"created_at\":\"2017-07-14T16:53:42Z\",\
+                                \"updated_at\":\"2017-08-17T12:37:15Z\"}";
+
+    #[test]
+    fn test_edit_team_parent() -> () {
+        let mut edit = EditTeam {
+            name: "Justice League".to_owned(),
+            ..Default::default()
+        };
+        assert_eq!(json_ops::to_json(&edit, "").unwrap(), "{\"name\":\"Justice League\"}");
+        edit.parent_team_id = Some(None);
+        assert_eq!(json_ops::to_json(&edit, "").unwrap(), "{\"name\":\"Justice League\",\"parent_team_id\":null}");
+        edit.parent_team_id = Some(Some(42));
+        assert_eq!(json_ops::to_json(&edit, "").unwrap(), "{\"name\":\"Justice League\",\"parent_team_id\":42}");
+    }
+
+    #[test]
+    fn test_team_membership_role_omitted() -> () {
+        assert_eq!(json_ops::to_json(&TeamMembershipRole { role: None }, "").unwrap(), "{}");
+        assert_eq!(json_ops::to_json(&TeamMembershipRole { role: Some("maintainer".to_owned()) }, "").unwrap(),
+                   "{\"role\":\"maintainer\"}");
+    }
+
+    #[test]
+    fn test_team_repository_permission_omitted() -> () {
+        assert_eq!(json_ops::to_json(&TeamRepositoryPermission { permission: None }, "").unwrap(), "{}");
+        assert_eq!(json_ops::to_json(&TeamRepositoryPermission { permission: Some("push".to_owned()) }, "").unwrap(),
+                   "{\"permission\":\"push\"}");
+    }
+
+    quickcheck! {
+        fn prop_team_optional_fields(choices: Vec<u8>, text: String) -> bool {
+            let nullable = ["description", "privacy", "html_url", "parent", "members_count", "repos_count",
+                            "created_at", "updated_at"];
+            let json = json_ops::vary_fields(TEAM, &nullable, &[], &choices, &text);
+            json_ops::from_str::<Team>(&json, "").is_ok()
+        }
+    }
+}
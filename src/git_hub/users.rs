@@ -0,0 +1,178 @@
+use error::GhResult;
+
+use evidence::json_ops;
+use git_hub::{Client, GitHubResponse};
+use git_hub::pagination;
+use git_hub::pagination::Pagination;
+
+use resources::users::{User, UserSummary};
+
+// The body of a request to edit the authenticated user's profile, only the
+// fields given are changed. An empty string clears a field.
+// See https://developer.github.com/v3/users/#update-the-authenticated-user
+#[derive(Default, Deserialize, Serialize)]
+pub struct EditUser {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bio: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blog: Option<String>
+}
+
+pub fn get_authed_user(client: &Client) -> GhResult<GitHubResponse> {
+    client.execute(&requests::GetAuthenticatedUser)
+}
+
+// The authenticated user's profile, private fields included
+pub fn get_authed_user_profile(client: &Client) -> GhResult<User> {
+    client.send(requests::GetAuthenticatedUser)
+}
+
+pub fn get_user(username: &str, client: &Client) -> GhResult<User> {
+    client.send(requests::GetUser {
+        username: username.to_owned()
+    })
+}
+
+pub fn edit_authed_user(edit: &EditUser, client: &Client) -> GhResult<User> {
+    client.send(requests::EditAuthenticatedUser {
+        body: json_ops::to_json(edit, SERIALIZE_EDIT_USER)?
+    })
+}
+
+// The followers of `username`, or of the authenticated user when None
+pub fn list_followers(username: Option<&str>,
+                      client: &Client,
+                      pagination: &Pagination) -> GhResult<Vec<UserSummary>> {
+    let request = requests::ListFollows {
+        username: username.map(|x| x.to_owned()),
+        path: "followers",
+        pagination: pagination.clone()
+    };
+    pagination::send_all_pages(client, request, pagination)
+}
+
+// The users `username`, or the authenticated user when None, follows
+pub fn list_following(username: Option<&str>,
+                      client: &Client,
+                      pagination: &Pagination) -> GhResult<Vec<UserSummary>> {
+    let request = requests::ListFollows {
+        username: username.map(|x| x.to_owned()),
+        path: "following",
+        pagination: pagination.clone()
+    };
+    pagination::send_all_pages(client, request, pagination)
+}
+
+const SERIALIZE_EDIT_USER: &'static str = "Error serializing GitHub User edit JSON.";
+
+mod requests {
+use config::Config;
+use error::GhResult;
+use git_hub::{parse_url, GitHubRequest};
+use git_hub::pagination::Pagination;
+use hyper::Url;
+use hyper::method::Method;
+use resources::users::{User, UserSummary};
+
+    // Gets the authenticated user
+    // GET /user
+    // See https://developer.github.com/v3/users/#get-the-authenticated-user
+    // for more information
+    pub struct GetAuthenticatedUser;
+
+    impl GitHubRequest for GetAuthenticatedUser {
+        type Response = User;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = String::with_capacity(50);
+            endpoint.push_str(config.api_url());
+            endpoint.push_str("/user");
+            parse_url(&endpoint)
+        }
+    }
+
+    // Gets a user
+    // GET /users/:username
+    // See https://developer.github.com/v3/users/#get-a-single-user
+    // for more information
+    pub struct GetUser {
+        pub username: String
+    }
+
+    impl GitHubRequest for GetUser {
+        type Response = User;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = String::with_capacity(50);
+            endpoint.push_str(config.api_url());
+            endpoint.push_str("/users/");
+            endpoint.push_str(&self.username);
+            parse_url(&endpoint)
+        }
+    }
+
+    // Updates the authenticated user
+    // PATCH /user
+    // See https://developer.github.com/v3/users/#update-the-authenticated-user
+    // for more information
+    pub struct EditAuthenticatedUser {
+        pub body: String
+    }
+
+    impl GitHubRequest for EditAuthenticatedUser {
+        type Response = User;
+
+        fn method(&self) -> Method {
+            Method::Patch
+        }
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = String::with_capacity(50);
+            endpoint.push_str(config.api_url());
+            endpoint.push_str("/user");
+            parse_url(&endpoint)
+        }
+
+        fn body(&self) -> Option<&str> {
+            Some(&self.body)
+        }
+    }
+
+    // Lists the followers of a user, or the users they follow
+    // GET /users/:username/followers
+    // GET /users/:username/following
+    // GET /user/followers
+    // GET /user/following
+    // See https://developer.github.com/v3/users/followers/
+    // for more information
+    pub struct ListFollows {
+        pub username: Option<String>,
+        pub path: &'static str,
+        pub pagination: Pagination
+    }
+
+    impl GitHubRequest for ListFollows {
+        type Response = Vec<UserSummary>;
+
+        fn url(&self, config: &Config) -> GhResult<Url> {
+            let mut endpoint = String::with_capacity(50);
+            endpoint.push_str(config.api_url());
+            match self.username {
+                Some(ref username) => {
+                    endpoint.push_str("/users/");
+                    endpoint.push_str(username);
+                },
+                None => endpoint.push_str("/user")
+            }
+            endpoint.push_str("/");
+            endpoint.push_str(self.path);
+            let mut url = parse_url(&endpoint)?;
+            self.pagination.append_query(&mut url);
+            Ok(url)
+        }
+    }
+}
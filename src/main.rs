@@ -15,18 +15,64 @@ extern crate hyper_native_tls;
 #[macro_use]
 extern crate version;
 
-use clap::{App, Arg, SubCommand};
+use clap::{App, AppSettings, Arg, SubCommand};
+
+use error::GhResult;
+
+use std::io::Write;
+use std::process;
 
 mod commands;
 mod config;
 mod git_hub;
 mod resources;
+mod editor;
+mod error;
 mod evidence;
+mod git;
 
 fn main() {
+    match run() {
+        Ok(())   => (),
+        Err(err) => {
+            let _ = writeln!(std::io::stderr(), "gh: {}", err);
+            process::exit(err.exit_code());
+        }
+    }
+}
+
+fn run() -> GhResult<()> {
     let matches = App::new("gh")
+        .global_setting(AppSettings::PropagateGlobalValuesDown)
+        .arg(Arg::with_name("profile")
+                  .long("profile")
+                  .global(true)
+                  .help("The credential profile to use. Defaults to $GH_PROFILE, then the profile chosen by `gh config use`.")
+                  .value_name("name")
+                  .takes_value(true))
+        .arg(Arg::with_name("config-path")
+                  .long("config")
+                  .global(true)
+                  .help("The credentials file to use. Defaults to $GH_CONFIG_DIR/credentials, $XDG_CONFIG_HOME/gh/credentials, then ~/.config/gh/credentials.")
+                  .value_name("path")
+                  .takes_value(true))
+        .arg(Arg::with_name("wait-for-rate-limit")
+                  .long("wait-for-rate-limit")
+                  .global(true)
+                  .help("Waits for GitHub's API rate limit to reset and retries, instead of failing."))
+        .arg(Arg::with_name("repo")
+                  .long("repo")
+                  .short("R")
+                  .global(true)
+                  .help("The repository to work on. Defaults to the GitHub remote of the current git checkout, upstream before origin, except for repo archive and delete.")
+                  .value_name("OWNER/REPO")
+                  .takes_value(true))
+        .subcommand(commands::issues::SUBCOMMAND())
         .subcommand(commands::orgs::SUBCOMMAND())
         .subcommand(commands::pullreqs::SUBCOMMAND())
+        .subcommand(commands::rate_limit::SUBCOMMAND())
+        .subcommand(commands::repos::SUBCOMMAND())
+        .subcommand(commands::users::SUBCOMMAND())
 	    .subcommand(SubCommand::with_name("config")
 								.about("View and Set GitHub Configuration")
 								.version(version!())
@@ -37,8 +83,18 @@ fn main() {
                                                                   .required(true)
                                                                   .help("GitHub user name"))
                                                         .arg(Arg::with_name("access_token")
-                                                                  .required(true)
-                                                                  .help("GitHub access token")))
+                                                                  .required_unless("credential-helper")
+                                                                  .help("GitHub access token"))
+                                                        .arg(Arg::with_name("credential-helper")
+                                                                  .long("credential-helper")
+                                                                  .help("A shell command that prints the GitHub access token, used instead of storing it")
+                                                                  .value_name("command")
+                                                                  .takes_value(true))
+                                                        .arg(Arg::with_name("host")
+                                                                  .long("host")
+                                                                  .help("GitHub Enterprise hostname or API URL, e.g. ghe.example.com or https://ghe.example.com/api/v3")
+                                                                  .value_name("host")
+                                                                  .takes_value(true)))
                                 .subcommand(SubCommand::with_name("show")
                                                         .about("Shows the current GitHub Credentials")
                                                         .arg(Arg::with_name("format")
@@ -46,7 +102,25 @@ fn main() {
                                                              .long("format")
                                                              .help("Sets the output format.")
                                                              .value_name("json")
-                                                             .takes_value(true))))
+                                                             .takes_value(true))
+                                                        .arg(Arg::with_name("show-token")
+                                                             .long("show-token")
+                                                             .help("Shows the full access token instead of masking it")))
+                                .subcommand(SubCommand::with_name("verify")
+                                                        .about("Checks the current GitHub Credentials against GitHub")
+                                                        .arg(Arg::with_name("format")
+                                                             .short("f")
+                                                             .long("format")
+                                                             .help("Sets the output format.")
+                                                             .value_name("json")
+                                                             .takes_value(true)))
+                                .subcommand(SubCommand::with_name("use")
+                                                        .about("Sets the default credential profile")
+                                                        .arg(Arg::with_name("name")
+                                                                  .required(true)
+                                                                  .help("The profile name")))
+                                .subcommand(SubCommand::with_name("list")
+                                                        .about("Lists the credential profiles")))
 	    .version(version!())
         .author("penland365 <Jeffrey.N.Davis@gmail.com>")
         .get_matches();
@@ -56,13 +130,26 @@ fn main() {
             match config_matches.subcommand() {
                 ("set", Some(set_matches))   => config::set_config(set_matches),
                 ("show", Some(show_matches)) => config::show_config(show_matches),
-                ("", None) => println!("No subcommand was used for config"),
+                ("verify", Some(verify_matches)) => config::verify_config(verify_matches),
+                ("use", Some(use_matches))   => config::use_profile(use_matches),
+                ("list", Some(list_matches)) => config::list_profiles(list_matches),
+                ("", None) => {
+                    println!("No subcommand was used for config");
+                    Ok(())
+                },
                 (_, _) => unreachable!()
             }
         },
         ("orgs", Some(orgs_matches)) => commands::orgs::handle(orgs_matches),
+        ("issue", Some(issue_matches)) => commands::issues::handle(issue_matches),
         ("pullreq", Some(pullreq_matches)) => commands::pullreqs::handle(pullreq_matches),
-        ("", None) => println!("NO SUBCOMMAND USED"),
+        ("rate-limit", Some(rate_limit_matches)) => commands::rate_limit::handle(rate_limit_matches),
+        ("repo", Some(repo_matches)) => commands::repos::handle(repo_matches),
+        ("user", Some(user_matches)) => commands::users::handle(user_matches),
+        ("", None) => {
+            println!("NO SUBCOMMAND USED");
+            Ok(())
+        },
         (_, _)     => unreachable!()
     }
 }
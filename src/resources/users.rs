@@ -1,13 +1,75 @@
 // GitHub users. See https://developer.github.com/v3/users/
-// A base GitHub User
+// A GitHub User's full profile. Anything a user can leave blank is null
+// until they fill it in, and the private counts, disk usage, two-factor
+// status and plan are only sent for the authenticated user.
 #[derive(Serialize, Deserialize)]
 pub struct User {
     pub login: String,
-    pub id: u32,
-    pub location: String,
-    pub bio: Option<String>
+    pub id: u64,
+    #[serde(default)]
+    pub node_id: Option<String>,
+    pub avatar_url: String,
+    pub gravatar_id: Option<String>,
+    pub url: String,
+    pub html_url: String,
+    // User, Organization or Bot
+    #[serde(rename = "type")]
+    pub user_type: String,
+    #[serde(default)]
+    pub site_admin: bool,
+    pub name: Option<String>,
+    pub company: Option<String>,
+    pub blog: Option<String>,
+    pub location: Option<String>,
+    pub email: Option<String>,
+    pub hireable: Option<bool>,
+    pub bio: Option<String>,
+    #[serde(default)]
+    pub twitter_username: Option<String>,
+    pub public_repos: u32,
+    pub public_gists: u32,
+    pub followers: u32,
+    pub following: u32,
+    pub created_at: String,
+    pub updated_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub private_gists: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_private_repos: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owned_private_repos: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_usage: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collaborators: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub two_factor_authentication: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan: Option<UserPlan>
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct UserPlan {
+    pub name: String,
+    pub space: u64,
+    pub private_repos: u32,
+    #[serde(default)]
+    pub collaborators: u32
+}
+
+// A GitHub User as listed, in followers for instance
+#[derive(Serialize, Deserialize)]
+pub struct UserSummary {
+    pub login: String,
+    pub id: u64,
+    pub html_url: String,
+    #[serde(rename = "type")]
+    pub user_type: String,
+    #[serde(default)]
+    pub site_admin: bool
+}
+
+#[cfg(test)]
 fn reverse<T: Clone>(xs: &[T]) -> Vec<T> {
     let mut rev = vec!();
     for x in xs.iter() {
@@ -18,6 +80,9 @@ fn reverse<T: Clone>(xs: &[T]) -> Vec<T> {
 
 #[cfg(test)]
 mod tests {
+use evidence::json_ops;
+use super::User;
+
     #[test]
     fn it_works() {
     }
@@ -28,4 +93,41 @@ mod tests {
             xs == reverse(&reverse(&xs))
         }
 	}
+
+    const USER: &'static str = "{\"login\":\"octocat\",\"id\":1,\"node_id\":\"MDQ6VXNlcjE=\",\
+                                \"avatar_url\":\"https://github.com/images/error/octocat_happy.gif\",\"gravatar_id\":\"\",\
+                                \"url\":\"https://api.github.com/users/octocat\",\"html_url\":\"https://github.com/octocat\",\
+                                \"type\":\"User\",\"site_admin\":false,\"name\":\"monalisa octocat\",\"company\":\"GitHub\",\
+                                \"blog\":\"https://github.com/blog\",\"location\":\"San Francisco\",\
+                                \"email\":\"octocat@github.com\",\"hireable\":false,\"bio\":\"There once was...\",\
+                                \"twitter_username\":\"monatheoctocat\",\"public_repos\":2,\"public_gists\":1,\
+                                \"followers\":20,\"following\":0,\"created_at\":\"2008-01-14T04:33:35Z\",\
+                                \"updated_at\":\"2008-01-14T04:33:35Z\",\"private_gists\":81,\"total_private_repos\":100,\
+                                \"owned_private_repos\":100,\"disk_usage\":10000,\"collaborators\":8,\
+                                \"two_factor_authentication\":true,\
+                                \"plan\":{\"name\":\"Medium\",\"space\":400,\"private_repos\":20,\"collaborators\":0}}";
+
+    quickcheck! {
+        fn prop_user_optional_fields(choices: Vec<u8>, text: String) -> bool {
+            let nullable = ["node_id", "gravatar_id", "name", "company", "blog", "location", "email", "hireable", "bio",
+                            "twitter_username", "private_gists", "total_private_repos", "owned_private_repos",
+                            "disk_usage", "collaborators", "two_factor_authentication", "plan"];
+            let json = json_ops::vary_fields(USER, &nullable, &["site_admin"], &choices, &text);
+            json_ops::from_str::<User>(&json, "").is_ok()
+        }
+    }
+
+    #[test]
+    fn test_deserialize_blank_profile() -> () {
+        let body = "{\"login\":\"octocat\",\"id\":1,\"avatar_url\":\"https://github.com/images/error/octocat_happy.gif\",\
+                    \"gravatar_id\":\"\",\"url\":\"https://api.github.com/users/octocat\",\
+                    \"html_url\":\"https://github.com/octocat\",\"type\":\"User\",\"site_admin\":false,\
+                    \"name\":null,\"company\":null,\"blog\":\"\",\"location\":null,\"email\":null,\"hireable\":null,\
+                    \"bio\":null,\"public_repos\":2,\"public_gists\":1,\"followers\":20,\"following\":0,\
+                    \"created_at\":\"2008-01-14T04:33:35Z\",\"updated_at\":\"2008-01-14T04:33:35Z\"}";
+        let user: User = json_ops::from_str(body, "").unwrap();
+        assert_eq!(user.location, None);
+        assert_eq!(user.user_type, "User");
+        assert!(user.plan.is_none());
+    }
 }